@@ -0,0 +1,27 @@
+CREATE TABLE saved_searches (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  owner TEXT NOT NULL,
+  name TEXT NOT NULL,
+  query JSONB NOT NULL,
+  schedule_seconds BIGINT NOT NULL DEFAULT 86400,
+  webhook_url TEXT,
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  last_run_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_saved_searches_owner
+  ON saved_searches(owner);
+
+CREATE INDEX idx_saved_searches_active_last_run
+  ON saved_searches(last_run_at)
+  WHERE active;
+
+CREATE TABLE saved_search_matches (
+  saved_search_id UUID NOT NULL REFERENCES saved_searches(id) ON DELETE CASCADE,
+  profile_id TEXT NOT NULL,
+  hash TEXT NOT NULL,
+  matched_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (saved_search_id, profile_id)
+);
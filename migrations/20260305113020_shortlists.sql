@@ -0,0 +1,37 @@
+CREATE TABLE shortlists (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  owner TEXT NOT NULL,
+  name TEXT NOT NULL,
+  description TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_shortlists_owner
+  ON shortlists(owner);
+
+CREATE TABLE shortlist_candidates (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  shortlist_id UUID NOT NULL REFERENCES shortlists(id) ON DELETE CASCADE,
+  profile_id TEXT NOT NULL,
+  stage TEXT NOT NULL DEFAULT 'shortlisted'
+    CHECK (stage IN ('shortlisted', 'contacted', 'interviewed', 'rejected', 'hired')),
+  notes TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (shortlist_id, profile_id)
+);
+
+CREATE INDEX idx_shortlist_candidates_profile_id
+  ON shortlist_candidates(profile_id, updated_at DESC);
+
+CREATE TABLE pipeline_events (
+  id BIGSERIAL PRIMARY KEY,
+  shortlist_candidate_id UUID NOT NULL REFERENCES shortlist_candidates(id) ON DELETE CASCADE,
+  stage TEXT NOT NULL,
+  notes TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_pipeline_events_candidate
+  ON pipeline_events(shortlist_candidate_id, created_at);
@@ -0,0 +1,11 @@
+CREATE TABLE profile_unlocks (
+  id BIGSERIAL PRIMARY KEY,
+  profile_id TEXT NOT NULL,
+  caller_id TEXT NOT NULL,
+  reason TEXT NOT NULL,
+  unlocked_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_profile_unlocks_lookup
+  ON profile_unlocks(profile_id, caller_id, expires_at DESC);
@@ -0,0 +1,20 @@
+CREATE TABLE api_keys (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  name TEXT NOT NULL,
+  key_prefix TEXT NOT NULL,
+  key_hash TEXT NOT NULL,
+  scopes TEXT[] NOT NULL DEFAULT '{}',
+  role TEXT NOT NULL DEFAULT 'standard'
+    CHECK (role IN ('standard', 'privileged')),
+  rate_limit_per_minute INTEGER,
+  expires_at TIMESTAMPTZ,
+  revoked_at TIMESTAMPTZ,
+  rotated_from UUID REFERENCES api_keys(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX idx_api_keys_key_prefix
+  ON api_keys(key_prefix);
+
+CREATE INDEX idx_api_keys_name
+  ON api_keys(name);
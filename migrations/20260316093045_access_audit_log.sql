@@ -0,0 +1,34 @@
+CREATE TABLE access_audit_log (
+  id BIGSERIAL PRIMARY KEY,
+  accessed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  caller_id TEXT NOT NULL,
+  auth_method TEXT NOT NULL,
+  caller_role TEXT NOT NULL,
+  action TEXT NOT NULL,
+  profile_ids TEXT[] NOT NULL DEFAULT '{}',
+  query JSONB,
+  client_ip TEXT
+);
+
+CREATE INDEX idx_access_audit_profile_ids ON access_audit_log USING GIN (profile_ids);
+CREATE INDEX idx_access_audit_accessed_at ON access_audit_log(accessed_at DESC);
+CREATE INDEX idx_access_audit_caller ON access_audit_log(caller_id, accessed_at DESC);
+
+-- Append-only: rows can never be updated, and only the retention purge
+-- (which sets audit.retention_purge for its transaction) may delete them.
+CREATE FUNCTION access_audit_log_guard() RETURNS trigger AS $$
+BEGIN
+  IF TG_OP = 'DELETE' AND current_setting('audit.retention_purge', true) = 'on' THEN
+    RETURN OLD;
+  END IF;
+  RAISE EXCEPTION 'access_audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER access_audit_log_append_only
+  BEFORE UPDATE OR DELETE ON access_audit_log
+  FOR EACH ROW EXECUTE FUNCTION access_audit_log_guard();
+
+CREATE TRIGGER access_audit_log_no_truncate
+  BEFORE TRUNCATE ON access_audit_log
+  FOR EACH STATEMENT EXECUTE FUNCTION access_audit_log_guard();
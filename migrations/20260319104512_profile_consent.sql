@@ -0,0 +1,30 @@
+CREATE TABLE profile_consents (
+  profile_id TEXT PRIMARY KEY,
+  purposes TEXT[] NOT NULL DEFAULT '{}',
+  granted_at TIMESTAMPTZ,
+  expires_at TIMESTAMPTZ,
+  withdrawn_at TIMESTAMPTZ,
+  source TEXT NOT NULL CHECK (source IN ('payload', 'feed')),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_profile_consents_purposes ON profile_consents USING GIN (purposes);
+
+-- Erased profile ids; syncs and consent updates skip anything listed here.
+CREATE TABLE profile_tombstones (
+  profile_id TEXT PRIMARY KEY,
+  erased_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  erased_by TEXT NOT NULL,
+  reason TEXT
+);
+
+CREATE FUNCTION has_valid_consent(p_profile_id TEXT, p_purpose TEXT) RETURNS BOOLEAN AS $$
+  SELECT EXISTS (
+    SELECT 1
+    FROM profile_consents
+    WHERE profile_id = p_profile_id
+      AND withdrawn_at IS NULL
+      AND (expires_at IS NULL OR expires_at > now())
+      AND p_purpose = ANY(purposes)
+  )
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,31 @@
+-- One row per profile sync, from the first search until every BPP has
+-- delivered its pages.
+CREATE TABLE sync_runs (
+  transaction_id TEXT PRIMARY KEY,
+  trigger TEXT NOT NULL CHECK (trigger IN ('cron', 'admin')),
+  bpp_id TEXT,
+  started_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  finished_at TIMESTAMPTZ,
+  cancelled_at TIMESTAMPTZ,
+  failure_reason TEXT
+);
+
+CREATE INDEX idx_sync_runs_started_at ON sync_runs (started_at DESC);
+
+-- Progress of each BPP that answered a sync. Not tied to sync_runs by a
+-- foreign key, so pages still count when the run row could not be written.
+CREATE TABLE sync_run_bpps (
+  transaction_id TEXT NOT NULL,
+  bpp_id TEXT NOT NULL,
+  pages_expected INTEGER,
+  pages_received INTEGER[] NOT NULL DEFAULT '{}',
+  items_received INTEGER NOT NULL DEFAULT 0,
+  inserted INTEGER NOT NULL DEFAULT 0,
+  updated INTEGER NOT NULL DEFAULT 0,
+  unchanged INTEGER NOT NULL DEFAULT 0,
+  stale_deleted INTEGER,
+  errors TEXT[] NOT NULL DEFAULT '{}',
+  started_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  finished_at TIMESTAMPTZ,
+  PRIMARY KEY (transaction_id, bpp_id)
+);
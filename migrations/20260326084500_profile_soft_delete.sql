@@ -0,0 +1,7 @@
+-- Profiles missing from a completed sync are soft-deleted first and purged
+-- after profile_sync.purge_after_days, so a bad sync can be undone.
+ALTER TABLE profiles ADD COLUMN deleted_at TIMESTAMPTZ;
+
+CREATE INDEX idx_profiles_deleted_at
+ON profiles (deleted_at)
+WHERE deleted_at IS NOT NULL;
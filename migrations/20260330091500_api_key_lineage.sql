@@ -0,0 +1,41 @@
+-- A key and every key rotated from it share one lineage id. Callers are
+-- identified as key:<lineage_id>, so ownership survives rotation.
+ALTER TABLE api_keys ADD COLUMN lineage_id UUID;
+
+WITH RECURSIVE lineage AS (
+  SELECT id, id AS root
+  FROM api_keys
+  WHERE rotated_from IS NULL
+  UNION ALL
+  SELECT k.id, l.root
+  FROM api_keys k
+  JOIN lineage l ON k.rotated_from = l.id
+)
+UPDATE api_keys k
+SET lineage_id = l.root
+FROM lineage l
+WHERE k.id = l.id;
+
+ALTER TABLE api_keys ALTER COLUMN lineage_id SET NOT NULL;
+
+-- Names were free to repeat; keep the oldest lineage per name and suffix
+-- the rest before the name becomes unique.
+UPDATE api_keys k
+SET name = k.name || '-' || left(k.lineage_id::text, 8)
+FROM (
+  SELECT id
+  FROM (
+    SELECT id, row_number() OVER (PARTITION BY name ORDER BY created_at, id) AS n
+    FROM api_keys
+    WHERE rotated_from IS NULL
+  ) roots
+  WHERE n > 1
+) dup
+WHERE k.lineage_id = dup.id;
+
+-- Rotation keeps the name, so uniqueness is enforced per lineage root.
+DROP INDEX idx_api_keys_name;
+
+CREATE UNIQUE INDEX idx_api_keys_name
+  ON api_keys(name)
+  WHERE rotated_from IS NULL;
@@ -0,0 +1,3 @@
+-- BPPs a run waits for before it finishes: the target of a per-BPP run, or
+-- every BPP with active profiles when a full run started.
+ALTER TABLE sync_runs ADD COLUMN expected_bpps TEXT[] NOT NULL DEFAULT '{}';
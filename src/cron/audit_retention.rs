@@ -0,0 +1,19 @@
+use crate::db::access_audit::purge_access_records;
+use crate::state::AppState;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub async fn run(app_state: Arc<AppState>) {
+    let retention_days = app_state.config.current().audit.retention_days;
+    if retention_days == 0 {
+        return;
+    }
+
+    match purge_access_records(&app_state.db_pool, retention_days).await {
+        Ok(count) => info!(
+            "🧾 Access log retention: {} records older than {} days purged",
+            count, retention_days
+        ),
+        Err(e) => error!("Access log retention purge failed: {:?}", e),
+    }
+}
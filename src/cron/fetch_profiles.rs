@@ -1,17 +1,68 @@
+use crate::db::sync_run::{fail_sync_run, insert_sync_run};
 use crate::models::search::{Intent, Pagination, SearchMessage};
+use crate::models::sync::SyncTrigger;
 use crate::state::AppState;
 use crate::utils::http_client::post_json;
 use crate::utils::logging::log_cron_job;
 use crate::utils::payload_generator::build_profile_beckn_request;
+use crate::utils::payload_log::log_payload;
+use anyhow::Context;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, field::Empty, info, instrument, Span};
 use uuid::Uuid;
 
+/// A single BPP to sync instead of the whole network.
+pub struct SyncTarget {
+    pub bpp_id: String,
+    pub bpp_uri: String,
+}
+
 pub async fn run(app_state: Arc<AppState>) {
     log_cron_job("🔄", "Starting fetch profiles cron. ");
 
+    match start_sync(&app_state, SyncTrigger::Cron, None).await {
+        Ok(SyncStart::Started(_)) => {}
+        Ok(SyncStart::AlreadyRunning(running)) => {
+            info!(target: "cron", "⏭️ Skipping sync, run {} is still in progress", running);
+        }
+        Err(e) => error!(target: "cron", "❌ Failed to start profile sync: {}", e),
+    }
+}
+
+pub enum SyncStart {
+    Started(String),
+    /// An overlapping run is still in its sync window; holds its
+    /// transaction ID.
+    AlreadyRunning(String),
+}
+
+/// Registers a sync run and sends the first search page to the BAP
+/// adapter. Later pages are requested from `handle_on_search`. Nothing is
+/// sent while an overlapping run, from either trigger, is still going.
+#[instrument(
+    name = "fetch_profiles",
+    skip_all,
+    fields(transaction_id = Empty, message_id = Empty, action = "search")
+)]
+pub async fn start_sync(
+    app_state: &AppState,
+    trigger: SyncTrigger,
+    target: Option<&SyncTarget>,
+) -> anyhow::Result<SyncStart> {
     let message_id = format!("msg-profile-{}", Uuid::new_v4());
-    let txn_id = format!("cron-profile-{}", Uuid::new_v4());
+    let txn_id = format!("{}-profile-{}", trigger.as_str(), Uuid::new_v4());
+    Span::current()
+        .record("transaction_id", &txn_id)
+        .record("message_id", &message_id);
+
+    let bpp_id = target.map(|t| t.bpp_id.as_str());
+    if let Some(running) = insert_sync_run(&app_state.db_pool, &txn_id, trigger, bpp_id)
+        .await
+        .context("failed to register sync run")?
+    {
+        return Ok(SyncStart::AlreadyRunning(running));
+    }
+
     let intent = Intent {
         item: None,
         provider: None,
@@ -26,22 +77,28 @@ pub async fn run(app_state: Arc<AppState>) {
         }),
     };
 
+    let config = app_state.config.current();
     let payload = build_profile_beckn_request(
-        &app_state.config,
+        &config,
         &txn_id,
         &message_id,
         &message,
         "search",
-        None,
-        None,
+        bpp_id,
+        target.map(|t| t.bpp_uri.as_str()),
     );
     // Send to BAP adapter (profile)
     info!(target: "cron", "📡 Sending search request to BAP adapter...");
-    info!(target: "cron", "Payload: {}", payload);
-    let adapter_url = format!("{}/search", app_state.config.bap.caller_uri);
+    log_payload("Payload", &payload, &config.logging.payloads);
+    let adapter_url = format!("{}/search", config.bap.caller_uri);
     if let Err(e) = post_json(&adapter_url, payload).await {
-        error!(target: "cron", "❌ Failed to send search to BAP adapter: {}", e);
-    } else {
-        info!(target: "cron", "📨 Search request sent to BAP adapter successfully");
+        let reason = format!("Search to BAP adapter failed: {e}");
+        if let Err(e) = fail_sync_run(&app_state.db_pool, &txn_id, &reason).await {
+            error!(target: "cron", "Failed to record sync failure {}: {:?}", txn_id, e);
+        }
+        return Err(e);
     }
+    info!(target: "cron", "📨 Search request sent to BAP adapter successfully");
+
+    Ok(SyncStart::Started(txn_id))
 }
@@ -1,50 +1,156 @@
-mod fetch_profiles;
+mod audit_retention;
+pub mod fetch_profiles;
+mod profile_purge;
+pub mod saved_searches;
+use crate::config::AppConfig;
 use crate::state::AppState;
-use crate::utils::cron::build_cron_expr;
+use crate::utils::cron::build_schedule;
+use crate::utils::metrics::record_cron_run;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::time::{sleep, Duration};
-use tokio_cron_scheduler::{Job, JobScheduler};
+use tokio::time::{sleep, Duration, Instant};
+use tokio_cron_scheduler::{JobScheduler, JobSchedulerError};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CronTask {
+    FetchProfiles,
+    SavedSearches,
+    AuditRetention,
+    ProfilePurge,
+}
+
+impl CronTask {
+    const ALL: [CronTask; 4] = [
+        CronTask::FetchProfiles,
+        CronTask::SavedSearches,
+        CronTask::AuditRetention,
+        CronTask::ProfilePurge,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            CronTask::FetchProfiles => "fetch_profiles",
+            CronTask::SavedSearches => "saved_searches",
+            CronTask::AuditRetention => "audit_retention",
+            CronTask::ProfilePurge => "profile_purge",
+        }
+    }
+
+    /// Interval in seconds under `config`, or `None` when the task is off.
+    fn interval(self, config: &AppConfig) -> Option<u64> {
+        match self {
+            CronTask::FetchProfiles => Some(config.cron.fetch_profiles.seconds),
+            CronTask::SavedSearches => config.cron.saved_searches.as_ref().map(|s| s.seconds),
+            CronTask::AuditRetention => {
+                (config.audit.retention_days > 0).then_some(config.audit.purge_interval_seconds)
+            }
+            CronTask::ProfilePurge => (config.profile_sync.purge_after_days > 0)
+                .then_some(config.profile_sync.purge_interval_seconds),
+        }
+    }
+
+    async fn run(self, state: Arc<AppState>) {
+        let started = Instant::now();
+        match self {
+            CronTask::FetchProfiles => fetch_profiles::run(state).await,
+            CronTask::SavedSearches => saved_searches::run(state).await,
+            CronTask::AuditRetention => audit_retention::run(state).await,
+            CronTask::ProfilePurge => profile_purge::run(state).await,
+        }
+        record_cron_run(self.name(), started.elapsed());
+    }
+}
+
+/// The scheduler and the jobs registered on it, kept so a config reload
+/// can move tasks to new intervals.
+pub struct CronJobs {
+    scheduler: JobScheduler,
+    state: Arc<AppState>,
+    jobs: HashMap<CronTask, (Uuid, u64)>,
+}
+
+impl CronJobs {
+    async fn schedule(&mut self, task: CronTask, seconds: u64) -> Result<(), JobSchedulerError> {
+        let (desc, schedule) = build_schedule(seconds);
+        tracing::info!(
+            "📅 Scheduling {} cron: {} → {}",
+            task.name(),
+            desc,
+            schedule
+        );
+
+        let state = self.state.clone();
+        let job = schedule.job(move |_uuid, _l| {
+            let state = state.clone();
+            Box::pin(async move {
+                let tasks = state.tasks.clone();
+                tasks.track_future(task.run(state)).await;
+            })
+        })?;
+
+        let id = self.scheduler.add(job).await?;
+        self.jobs.insert(task, (id, seconds));
+        Ok(())
+    }
+
+    /// Brings the registered jobs in line with the current config: adds
+    /// newly enabled tasks, moves changed intervals and drops disabled ones.
+    pub async fn reschedule(&mut self) -> Result<(), JobSchedulerError> {
+        let config = self.state.config.current();
+
+        for task in CronTask::ALL {
+            let wanted = task.interval(&config);
+            let current = self.jobs.get(&task).copied();
+            if current.map(|(_, seconds)| seconds) == wanted {
+                continue;
+            }
+
+            if let Some((id, _)) = current {
+                self.scheduler.remove(&id).await?;
+                self.jobs.remove(&task);
+                tracing::info!("🗓️ Unscheduled {} cron", task.name());
+            }
+            if let Some(seconds) = wanted {
+                self.schedule(task, seconds).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stops triggering jobs. Runs already in progress are tracked in
+    /// `AppState::tasks` and finish on their own.
+    pub async fn shutdown(&mut self) -> Result<(), JobSchedulerError> {
+        self.scheduler.shutdown().await?;
+        self.jobs.clear();
+        Ok(())
+    }
+}
+
 pub async fn start_cron_jobs(
     state: Arc<AppState>,
-) -> Result<JobScheduler, Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<CronJobs, Box<dyn std::error::Error + Send + Sync>> {
     let scheduler = JobScheduler::new().await?;
 
     {
         let state = state.clone();
-        tokio::spawn(async move {
+        state.tasks.clone().spawn(async move {
             tracing::info!("🚀 Server restarted, waiting 5 seconds before first fetch_profiles...");
             sleep(Duration::from_secs(5)).await;
 
             tracing::info!("👤 Running initial fetch_profiles...");
-            fetch_profiles::run(state).await;
+            CronTask::FetchProfiles.run(state).await;
         });
     }
 
-    let (profiles_desc, profiles_cron_expr) =
-        build_cron_expr(state.config.cron.fetch_profiles.seconds);
-
-    tracing::info!(
-        "📅 Scheduling fetch_profiles cron: {} → {}",
-        profiles_desc,
-        profiles_cron_expr
-    );
-
-    scheduler
-        .add(
-            Job::new_async(&profiles_cron_expr, {
-                let state = state.clone();
-                move |_uuid, _l| {
-                    let state = state.clone();
-                    Box::pin(async move {
-                        fetch_profiles::run(state).await;
-                    })
-                }
-            })
-            .unwrap(),
-        )
-        .await
-        .unwrap();
-    scheduler.start().await?;
+    let mut cron_jobs = CronJobs {
+        scheduler,
+        state,
+        jobs: HashMap::new(),
+    };
+    cron_jobs.reschedule().await?;
+    cron_jobs.scheduler.start().await?;
 
-    Ok(scheduler)
+    Ok(cron_jobs)
 }
@@ -0,0 +1,19 @@
+use crate::db::profile::purge_deleted_profiles;
+use crate::state::AppState;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub async fn run(app_state: Arc<AppState>) {
+    let purge_after_days = app_state.config.current().profile_sync.purge_after_days;
+    if purge_after_days == 0 {
+        return;
+    }
+
+    match purge_deleted_profiles(&app_state.db_pool, purge_after_days).await {
+        Ok(count) => info!(
+            "🗑️ Profile purge: {} profiles soft-deleted over {} days ago removed",
+            count, purge_after_days
+        ),
+        Err(e) => error!("Profile purge failed: {:?}", e),
+    }
+}
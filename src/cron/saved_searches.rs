@@ -0,0 +1,153 @@
+use crate::db::profile::TalentFilter;
+use crate::db::saved_search::{
+    fetch_due_saved_searches, find_new_matches, mark_saved_search_run, record_matches, SavedSearch,
+};
+use crate::models::auth::CallerRole;
+use crate::models::search::TalentSearchRequest;
+use crate::services::profile::build_talent_search_params;
+use crate::state::AppState;
+use crate::utils::audit::record_system_access;
+use crate::utils::http_client::post_json_expect_success;
+use crate::utils::logging::log_cron_job;
+use crate::utils::redaction::RedactionView;
+use chrono::Utc;
+use deadpool_redis::redis::AsyncCommands;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+const LOCK_KEY: &str = "saved_searches:lock";
+const LOCK_TTL_SECONDS: u64 = 300;
+const MAX_ALERT_CANDIDATES: u32 = 500;
+
+pub async fn run(app_state: Arc<AppState>) {
+    let mut redis = match app_state.redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            error!(target: "cron", "Redis connection failed: {}", e);
+            return;
+        }
+    };
+
+    let locked: bool = redis
+        .set_nx(LOCK_KEY, Utc::now().to_rfc3339())
+        .await
+        .unwrap_or(false);
+    if !locked {
+        info!(target: "cron", "Saved search evaluation already running, skipping");
+        return;
+    }
+    let _: Result<(), _> = redis.expire(LOCK_KEY, LOCK_TTL_SECONDS as i64).await;
+
+    log_cron_job("🔔", "Evaluating saved searches. ");
+
+    match fetch_due_saved_searches(&app_state.db_pool).await {
+        Ok(searches) => {
+            for search in searches {
+                evaluate(&app_state, &search).await;
+            }
+        }
+        Err(e) => error!(target: "cron", "fetch_due_saved_searches failed: {}", e),
+    }
+
+    let _: Result<(), _> = redis.del(LOCK_KEY).await;
+}
+
+async fn evaluate(app_state: &AppState, search: &SavedSearch) {
+    let config = app_state.config.current();
+    // Re-checked on every run: the allow-list may have changed since the
+    // search was saved.
+    if let Some(url) = search.webhook_url.as_deref() {
+        if let Err(problem) = config.saved_searches.check_webhook_url(url) {
+            warn!(target: "cron", "Saved search {} skipped: {}", search.id, problem);
+            return;
+        }
+    }
+    let Some(webhook_url) = search
+        .webhook_url
+        .clone()
+        .or_else(|| config.saved_searches.webhook_url.clone())
+    else {
+        warn!(
+            target: "cron",
+            "Saved search {} has no webhook_url and no default is configured", search.id
+        );
+        return;
+    };
+
+    let req: TalentSearchRequest = match serde_json::from_value(search.query.clone()) {
+        Ok(req) => req,
+        Err(e) => {
+            error!(target: "cron", "Saved search {} has an invalid query: {}", search.id, e);
+            return;
+        }
+    };
+    let params = build_talent_search_params(&req, config.consent.search_purpose());
+
+    let matches = match find_new_matches(
+        &app_state.db_pool,
+        search.id,
+        &TalentFilter::from(&params),
+        // Alerts leave the service, so they get the standard caller's view.
+        &RedactionView::new(&config.redaction, CallerRole::Standard),
+        MAX_ALERT_CANDIDATES,
+    )
+    .await
+    {
+        Ok(m) => m,
+        Err(e) => {
+            error!(target: "cron", "find_new_matches failed for {}: {}", search.id, e);
+            return;
+        }
+    };
+
+    if !matches.is_empty() {
+        let payload = serde_json::json!({
+            "saved_search_id": search.id,
+            "name": search.name,
+            "owner": search.owner,
+            "run_at": Utc::now().to_rfc3339(),
+            "previous_run_at": search.last_run_at,
+            "count": matches.len(),
+            "candidates": matches,
+        });
+
+        let delivery = post_json_expect_success(&webhook_url, payload).await;
+        // Recorded whether or not the receiver acknowledged it, since the
+        // candidates may have reached it either way.
+        record_system_access(
+            app_state,
+            &search.owner,
+            "saved_search_alert",
+            matches
+                .iter()
+                .map(|m| m.candidate.profile_id.clone())
+                .collect(),
+            Some(serde_json::json!({
+                "saved_search_id": search.id,
+                "webhook_url": webhook_url,
+                "delivered": delivery.is_ok(),
+                "query": search.query,
+            })),
+        )
+        .await;
+        if let Err(e) = delivery {
+            error!(target: "cron", "Failed to deliver alert for saved search {}: {}", search.id, e);
+            return;
+        }
+
+        if let Err(e) = record_matches(&app_state.db_pool, search.id, &matches).await {
+            error!(target: "cron", "record_matches failed for {}: {}", search.id, e);
+            return;
+        }
+
+        info!(
+            target: "cron",
+            "📨 Delivered {} candidates for saved search {} ({})",
+            matches.len(), search.id, search.name
+        );
+    }
+
+    if let Err(e) = mark_saved_search_run(&app_state.db_pool, search.id).await {
+        error!(target: "cron", "mark_saved_search_run failed for {}: {}", search.id, e);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::db::profile::PaginatedItems;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{query, query_scalar, PgPool, Row};
+
+pub struct NewAccessRecord<'a> {
+    pub caller_id: &'a str,
+    pub auth_method: &'a str,
+    pub caller_role: &'a str,
+    pub action: &'a str,
+    pub profile_ids: Vec<String>,
+    pub query: Option<Value>,
+    pub client_ip: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccessRecord {
+    pub id: i64,
+    pub accessed_at: DateTime<Utc>,
+    pub caller_id: String,
+    pub auth_method: String,
+    pub caller_role: String,
+    pub action: String,
+    pub profile_ids: Vec<String>,
+    pub query: Option<Value>,
+    pub client_ip: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct AccessRecordFilter {
+    pub profile_id: Option<String>,
+    pub caller_id: Option<String>,
+    pub action: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub page: u32,
+    pub limit: u32,
+}
+
+pub async fn insert_access_record(
+    db_pool: &PgPool,
+    record: &NewAccessRecord<'_>,
+) -> Result<(), sqlx::Error> {
+    query(
+        r#"
+        INSERT INTO access_audit_log
+            (caller_id, auth_method, caller_role, action, profile_ids, query, client_ip)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(record.caller_id)
+    .bind(record.auth_method)
+    .bind(record.caller_role)
+    .bind(record.action)
+    .bind(&record.profile_ids)
+    .bind(&record.query)
+    .bind(&record.client_ip)
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn list_access_records(
+    db_pool: &PgPool,
+    filter: &AccessRecordFilter,
+) -> Result<PaginatedItems<AccessRecord>, sqlx::Error> {
+    let page = filter.page.max(1);
+    let limit = filter.limit.clamp(1, 500);
+    let offset = (page - 1) * limit;
+
+    let where_clause = r#"
+        WHERE ($1::TEXT IS NULL OR profile_ids @> ARRAY[$1::TEXT])
+          AND ($2::TEXT IS NULL OR caller_id = $2)
+          AND ($3::TEXT IS NULL OR action = $3)
+          AND ($4::TIMESTAMPTZ IS NULL OR accessed_at >= $4)
+          AND ($5::TIMESTAMPTZ IS NULL OR accessed_at < $5)
+    "#;
+
+    let total: i64 = query_scalar(&format!(
+        "SELECT COUNT(*) FROM access_audit_log {where_clause}"
+    ))
+    .bind(&filter.profile_id)
+    .bind(&filter.caller_id)
+    .bind(&filter.action)
+    .bind(filter.from)
+    .bind(filter.to)
+    .fetch_one(db_pool)
+    .await?;
+
+    let rows = query(&format!(
+        r#"
+        SELECT id, accessed_at, caller_id, auth_method, caller_role, action,
+               profile_ids, query, client_ip
+        FROM access_audit_log
+        {where_clause}
+        ORDER BY accessed_at DESC, id DESC
+        LIMIT $6 OFFSET $7
+        "#
+    ))
+    .bind(&filter.profile_id)
+    .bind(&filter.caller_id)
+    .bind(&filter.action)
+    .bind(filter.from)
+    .bind(filter.to)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(db_pool)
+    .await?;
+
+    let items = rows
+        .into_iter()
+        .map(|r| {
+            Ok(AccessRecord {
+                id: r.try_get("id")?,
+                accessed_at: r.try_get("accessed_at")?,
+                caller_id: r.try_get("caller_id")?,
+                auth_method: r.try_get("auth_method")?,
+                caller_role: r.try_get("caller_role")?,
+                action: r.try_get("action")?,
+                profile_ids: r.try_get("profile_ids")?,
+                query: r.try_get("query")?,
+                client_ip: r.try_get("client_ip")?,
+            })
+        })
+        .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+    Ok(PaginatedItems {
+        items,
+        total,
+        page,
+        limit,
+    })
+}
+
+/// Deletes records older than `retention_days`. The append-only trigger
+/// only lets deletes through inside a transaction flagged as a purge.
+pub async fn purge_access_records(
+    db_pool: &PgPool,
+    retention_days: u32,
+) -> Result<u64, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+
+    query("SELECT set_config('audit.retention_purge', 'on', true)")
+        .execute(&mut *tx)
+        .await?;
+
+    let deleted = query(
+        r#"
+        DELETE FROM access_audit_log
+        WHERE accessed_at < now() - $1 * INTERVAL '1 day'
+        "#,
+    )
+    .bind(retention_days as i32)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+    Ok(deleted)
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{postgres::PgRow, query, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct ApiKeyRecord {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    #[serde(skip)]
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub role: String,
+    pub rate_limit_per_minute: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub rotated_from: Option<Uuid>,
+    /// Shared by a key and every key rotated from it.
+    pub lineage_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct NewApiKey<'a> {
+    pub name: &'a str,
+    pub key_prefix: &'a str,
+    pub key_hash: &'a str,
+    pub scopes: Vec<String>,
+    pub role: &'a str,
+    pub rate_limit_per_minute: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub rotated_from: Option<Uuid>,
+}
+
+fn map_api_key(r: PgRow) -> Result<ApiKeyRecord, sqlx::Error> {
+    Ok(ApiKeyRecord {
+        id: r.try_get("id")?,
+        name: r.try_get("name")?,
+        key_prefix: r.try_get("key_prefix")?,
+        key_hash: r.try_get("key_hash")?,
+        scopes: r.try_get("scopes")?,
+        role: r.try_get("role")?,
+        rate_limit_per_minute: r.try_get("rate_limit_per_minute")?,
+        expires_at: r.try_get("expires_at")?,
+        revoked_at: r.try_get("revoked_at")?,
+        rotated_from: r.try_get("rotated_from")?,
+        lineage_id: r.try_get("lineage_id")?,
+        created_at: r.try_get("created_at")?,
+    })
+}
+
+pub async fn insert_api_key(
+    db_pool: &PgPool,
+    new: &NewApiKey<'_>,
+) -> Result<ApiKeyRecord, sqlx::Error> {
+    // A rotated key joins its predecessor's lineage; a new key starts its own.
+    let row = query(
+        r#"
+        WITH new_key AS (SELECT gen_random_uuid() AS id)
+        INSERT INTO api_keys (
+            id, name, key_prefix, key_hash, scopes, role,
+            rate_limit_per_minute, expires_at, rotated_from, lineage_id
+        )
+        SELECT id, $1, $2, $3, $4, $5, $6, $7, $8,
+               COALESCE((SELECT lineage_id FROM api_keys WHERE id = $8), id)
+        FROM new_key
+        RETURNING *
+        "#,
+    )
+    .bind(new.name)
+    .bind(new.key_prefix)
+    .bind(new.key_hash)
+    .bind(&new.scopes)
+    .bind(new.role)
+    .bind(new.rate_limit_per_minute)
+    .bind(new.expires_at)
+    .bind(new.rotated_from)
+    .fetch_one(db_pool)
+    .await?;
+
+    map_api_key(row)
+}
+
+pub async fn find_active_api_key_by_prefix(
+    db_pool: &PgPool,
+    key_prefix: &str,
+) -> Result<Option<ApiKeyRecord>, sqlx::Error> {
+    let row = query(
+        r#"
+        SELECT *
+        FROM api_keys
+        WHERE key_prefix = $1
+          AND revoked_at IS NULL
+          AND (expires_at IS NULL OR expires_at > now())
+        "#,
+    )
+    .bind(key_prefix)
+    .fetch_optional(db_pool)
+    .await?;
+
+    row.map(map_api_key).transpose()
+}
+
+pub async fn get_api_key(db_pool: &PgPool, id: Uuid) -> Result<Option<ApiKeyRecord>, sqlx::Error> {
+    let row = query("SELECT * FROM api_keys WHERE id = $1")
+        .bind(id)
+        .fetch_optional(db_pool)
+        .await?;
+
+    row.map(map_api_key).transpose()
+}
+
+pub async fn list_api_keys(db_pool: &PgPool) -> Result<Vec<ApiKeyRecord>, sqlx::Error> {
+    let rows = query("SELECT * FROM api_keys ORDER BY created_at DESC")
+        .fetch_all(db_pool)
+        .await?;
+
+    rows.into_iter().map(map_api_key).collect()
+}
+
+pub async fn revoke_api_key(db_pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = query(
+        r#"
+        UPDATE api_keys
+        SET revoked_at = now()
+        WHERE id = $1
+          AND revoked_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .execute(db_pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn expire_api_key(
+    db_pool: &PgPool,
+    id: Uuid,
+    grace_seconds: u64,
+) -> Result<(), sqlx::Error> {
+    query(
+        r#"
+        UPDATE api_keys
+        SET expires_at = LEAST(
+                COALESCE(expires_at, 'infinity'::timestamptz),
+                now() + $2 * INTERVAL '1 second'
+            )
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(grace_seconds as i64)
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
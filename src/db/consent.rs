@@ -0,0 +1,201 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{query, query_scalar, PgPool, Row};
+
+pub struct NewConsent {
+    pub profile_id: String,
+    pub purposes: Vec<String>,
+    pub granted_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub withdrawn: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileConsent {
+    pub profile_id: String,
+    pub purposes: Vec<String>,
+    pub granted_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub source: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErasureResult {
+    pub profile_id: String,
+    pub already_erased: bool,
+    pub profiles_deleted: u64,
+    pub shortlist_entries_deleted: u64,
+    pub pipeline_events_deleted: u64,
+    pub saved_search_matches_deleted: u64,
+    pub unlocks_deleted: u64,
+}
+
+/// Upserts consent rows, skipping tombstoned profiles. Feed records are
+/// authoritative: a later payload sync never overwrites consent that came
+/// from the feed.
+pub async fn upsert_consents(
+    db_pool: &PgPool,
+    consents: &[NewConsent],
+    source: &str,
+) -> Result<u64, sqlx::Error> {
+    if consents.is_empty() {
+        return Ok(0);
+    }
+
+    let profile_ids: Vec<&str> = consents.iter().map(|c| c.profile_id.as_str()).collect();
+    // Arrays of arrays do not survive UNNEST, so purposes travel as JSON.
+    let purposes: Vec<Value> = consents
+        .iter()
+        .map(|c| Value::from(c.purposes.clone()))
+        .collect();
+    let granted_at: Vec<Option<DateTime<Utc>>> = consents.iter().map(|c| c.granted_at).collect();
+    let expires_at: Vec<Option<DateTime<Utc>>> = consents.iter().map(|c| c.expires_at).collect();
+    let withdrawn: Vec<bool> = consents.iter().map(|c| c.withdrawn).collect();
+
+    let result = query(
+        r#"
+        INSERT INTO profile_consents
+            (profile_id, purposes, granted_at, expires_at, withdrawn_at, source, updated_at)
+        SELECT
+            t.profile_id,
+            ARRAY(SELECT jsonb_array_elements_text(t.purposes)),
+            t.granted_at,
+            t.expires_at,
+            CASE WHEN t.withdrawn THEN now() END,
+            $6,
+            now()
+        FROM UNNEST(
+            $1::text[],
+            $2::jsonb[],
+            $3::timestamptz[],
+            $4::timestamptz[],
+            $5::bool[]
+        ) AS t(profile_id, purposes, granted_at, expires_at, withdrawn)
+        WHERE NOT EXISTS (
+            SELECT 1 FROM profile_tombstones tb WHERE tb.profile_id = t.profile_id
+        )
+        ON CONFLICT (profile_id) DO UPDATE
+        SET
+            purposes = EXCLUDED.purposes,
+            granted_at = EXCLUDED.granted_at,
+            expires_at = EXCLUDED.expires_at,
+            withdrawn_at = CASE
+                WHEN EXCLUDED.withdrawn_at IS NULL THEN NULL
+                ELSE COALESCE(profile_consents.withdrawn_at, EXCLUDED.withdrawn_at)
+            END,
+            source = EXCLUDED.source,
+            updated_at = now()
+        WHERE profile_consents.source = 'payload' OR EXCLUDED.source = 'feed'
+        "#,
+    )
+    .bind(&profile_ids)
+    .bind(&purposes)
+    .bind(&granted_at)
+    .bind(&expires_at)
+    .bind(&withdrawn)
+    .bind(source)
+    .execute(db_pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn get_consent(
+    db_pool: &PgPool,
+    profile_id: &str,
+) -> Result<Option<ProfileConsent>, sqlx::Error> {
+    let row = query(
+        r#"
+        SELECT profile_id, purposes, granted_at, expires_at, withdrawn_at, source, updated_at
+        FROM profile_consents
+        WHERE profile_id = $1
+        "#,
+    )
+    .bind(profile_id)
+    .fetch_optional(db_pool)
+    .await?;
+
+    row.map(|r| {
+        Ok(ProfileConsent {
+            profile_id: r.try_get("profile_id")?,
+            purposes: r.try_get("purposes")?,
+            granted_at: r.try_get("granted_at")?,
+            expires_at: r.try_get("expires_at")?,
+            withdrawn_at: r.try_get("withdrawn_at")?,
+            source: r.try_get("source")?,
+            updated_at: r.try_get("updated_at")?,
+        })
+    })
+    .transpose()
+}
+
+pub async fn has_valid_consent(
+    db_pool: &PgPool,
+    profile_id: &str,
+    purpose: &str,
+) -> Result<bool, sqlx::Error> {
+    query_scalar("SELECT has_valid_consent($1, $2)")
+        .bind(profile_id)
+        .bind(purpose)
+        .fetch_one(db_pool)
+        .await
+}
+
+/// Removes every trace of `profile_id` we hold for recruiting purposes and
+/// tombstones it so later syncs do not bring it back. The access audit log
+/// is kept; it only references the id.
+pub async fn erase_profile(
+    db_pool: &PgPool,
+    profile_id: &str,
+    erased_by: &str,
+    reason: Option<&str>,
+) -> Result<ErasureResult, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+
+    let inserted = query(
+        r#"
+        INSERT INTO profile_tombstones (profile_id, erased_by, reason)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (profile_id) DO NOTHING
+        "#,
+    )
+    .bind(profile_id)
+    .bind(erased_by)
+    .bind(reason)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let mut deleted = Vec::with_capacity(6);
+    for sql in [
+        "DELETE FROM profiles WHERE profile_id = $1",
+        "DELETE FROM profile_consents WHERE profile_id = $1",
+        "DELETE FROM pipeline_events WHERE shortlist_candidate_id IN \
+         (SELECT id FROM shortlist_candidates WHERE profile_id = $1)",
+        "DELETE FROM shortlist_candidates WHERE profile_id = $1",
+        "DELETE FROM saved_search_matches WHERE profile_id = $1",
+        "DELETE FROM profile_unlocks WHERE profile_id = $1",
+    ] {
+        let count = query(sql)
+            .bind(profile_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        deleted.push(count);
+    }
+
+    tx.commit().await?;
+
+    Ok(ErasureResult {
+        profile_id: profile_id.to_string(),
+        already_erased: inserted == 0,
+        profiles_deleted: deleted[0],
+        pipeline_events_deleted: deleted[2],
+        shortlist_entries_deleted: deleted[3],
+        saved_search_matches_deleted: deleted[4],
+        unlocks_deleted: deleted[5],
+    })
+}
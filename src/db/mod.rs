@@ -1 +1,8 @@
+pub mod access_audit;
+pub mod api_key;
+pub mod consent;
 pub mod profile;
+pub mod profile_unlock;
+pub mod saved_search;
+pub mod shortlist;
+pub mod sync_run;
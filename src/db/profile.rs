@@ -1,8 +1,14 @@
+use crate::db::shortlist::{get_pipeline_statuses, PipelineStatus};
 use crate::models::search::Pagination;
+use crate::utils::encryption::{mask_envelopes, Keyring};
+use crate::utils::redaction::RedactionView;
 use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
 use serde::Serialize;
 use serde_json::Value;
-use sqlx::{query, query_scalar, Error, PgPool, Row};
+use sqlx::{
+    postgres::PgArguments, query, query::Query, query_scalar, Error, PgPool, Postgres, Row,
+};
 use tracing::info;
 
 pub struct NewProfile {
@@ -24,9 +30,21 @@ pub struct PaginatedItems<T = serde_json::Value> {
     pub limit: u32,
 }
 
-pub async fn store_profiles(db_pool: &PgPool, profiles: &[NewProfile]) -> Result<(), Error> {
+/// What `store_profiles` did with a batch. Tombstoned profiles are skipped
+/// and counted nowhere.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct StoreCounts {
+    pub inserted: u64,
+    pub updated: u64,
+    pub unchanged: u64,
+}
+
+pub async fn store_profiles(
+    db_pool: &PgPool,
+    profiles: &[NewProfile],
+) -> Result<StoreCounts, Error> {
     if profiles.is_empty() {
-        return Ok(());
+        return Ok(StoreCounts::default());
     }
     let profile_ids: Vec<&str> = profiles.iter().map(|p| p.profile_id.as_str()).collect();
 
@@ -48,8 +66,14 @@ pub async fn store_profiles(db_pool: &PgPool, profiles: &[NewProfile]) -> Result
 
     let bpp_uris: Vec<&str> = profiles.iter().map(|p| p.bpp_uri.as_str()).collect();
 
-    query(
+    // Every part of the statement sees the same snapshot, so `existing`
+    // holds the hashes from before the upsert.
+    let row = query(
         r#"
+        WITH existing AS (
+            SELECT profile_id, hash FROM profiles WHERE profile_id = ANY($1::text[])
+        ),
+        upserted AS (
         INSERT INTO profiles (
             profile_id,
             beckn_structure,
@@ -88,6 +112,9 @@ pub async fn store_profiles(db_pool: &PgPool, profiles: &[NewProfile]) -> Result
             bpp_id,
             bpp_uri
         )
+        WHERE NOT EXISTS (
+            SELECT 1 FROM profile_tombstones tb WHERE tb.profile_id = t.profile_id
+        )
         ON CONFLICT (profile_id) DO UPDATE
         SET
             beckn_structure = CASE
@@ -103,7 +130,16 @@ pub async fn store_profiles(db_pool: &PgPool, profiles: &[NewProfile]) -> Result
             transaction_id = EXCLUDED.transaction_id,
             bpp_id = EXCLUDED.bpp_id,
             bpp_uri = EXCLUDED.bpp_uri,
-            last_synced_at = EXCLUDED.last_synced_at
+            last_synced_at = EXCLUDED.last_synced_at,
+            deleted_at = NULL
+        RETURNING profile_id, hash
+        )
+        SELECT
+            COUNT(*) FILTER (WHERE e.profile_id IS NULL) AS inserted,
+            COUNT(*) FILTER (WHERE e.hash IS DISTINCT FROM u.hash AND e.profile_id IS NOT NULL) AS updated,
+            COUNT(*) FILTER (WHERE e.hash = u.hash) AS unchanged
+        FROM upserted u
+        LEFT JOIN existing e USING (profile_id)
         "#,
     )
     .bind(&profile_ids)
@@ -114,15 +150,20 @@ pub async fn store_profiles(db_pool: &PgPool, profiles: &[NewProfile]) -> Result
     .bind(&transaction_ids)
     .bind(&bpp_ids)
     .bind(&bpp_uris)
-    .execute(db_pool)
+    .fetch_one(db_pool)
     .await?;
 
-    Ok(())
+    Ok(StoreCounts {
+        inserted: row.try_get::<i64, _>("inserted")? as u64,
+        updated: row.try_get::<i64, _>("updated")? as u64,
+        unchanged: row.try_get::<i64, _>("unchanged")? as u64,
+    })
 }
 
 pub async fn fetch_profiles(
     db_pool: &PgPool,
     pagination: Pagination,
+    consent_purpose: Option<&str>,
 ) -> Result<PaginatedItems, sqlx::Error> {
     let page = pagination.page.unwrap_or(1).max(1);
     let limit = pagination.limit.unwrap_or(20).clamp(1, 1000);
@@ -138,8 +179,11 @@ pub async fn fetch_profiles(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
+          AND ($1::text IS NULL OR has_valid_consent(profile_id, $1))
         "#,
     )
+    .bind(consent_purpose)
     .fetch_one(db_pool)
     .await?;
 
@@ -155,6 +199,8 @@ pub async fn fetch_profiles(
             updated_at
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
+          AND ($3::text IS NULL OR has_valid_consent(profile_id, $3))
         ORDER BY updated_at DESC, profile_id DESC
         LIMIT $1
         OFFSET $2
@@ -162,6 +208,7 @@ pub async fn fetch_profiles(
     )
     .bind(limit as i64)
     .bind(offset as i64)
+    .bind(consent_purpose)
     .fetch_all(db_pool)
     .await?;
 
@@ -183,11 +230,45 @@ pub async fn fetch_profiles(
     Ok(PaginatedItems {
         items,
         total,
-        page: page as u32,
-        limit: limit as u32,
+        page,
+        limit,
     })
 }
 
+/// The URI stored with any of `bpp_id`'s profiles.
+pub async fn get_bpp_uri(db_pool: &PgPool, bpp_id: &str) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT bpp_uri FROM profiles WHERE bpp_id = $1 LIMIT 1")
+        .bind(bpp_id)
+        .fetch_optional(db_pool)
+        .await
+}
+
+/// Active profiles of `bpp_id`, and how many of them `txn_id` delivered.
+pub async fn count_sync_coverage(
+    db_pool: &PgPool,
+    bpp_id: &str,
+    txn_id: &str,
+) -> Result<(i64, i64), sqlx::Error> {
+    let row = query(
+        r#"
+        SELECT
+            COUNT(*) AS active,
+            COUNT(*) FILTER (WHERE transaction_id = $2) AS seen
+        FROM profiles
+        WHERE bpp_id = $1
+          AND deleted_at IS NULL
+        "#,
+    )
+    .bind(bpp_id)
+    .bind(txn_id)
+    .fetch_one(db_pool)
+    .await?;
+
+    Ok((row.try_get("active")?, row.try_get("seen")?))
+}
+
+/// Soft-deletes the profiles of `bpp_id` that `txn_id` did not deliver. They
+/// stay out of every query and come back if a later sync delivers them.
 pub async fn delete_stale_profiles(
     db_pool: &PgPool,
     bpp_id: &str,
@@ -195,9 +276,11 @@ pub async fn delete_stale_profiles(
 ) -> Result<u64, sqlx::Error> {
     let result = query(
         r#"
-        DELETE FROM profiles
+        UPDATE profiles
+        SET deleted_at = now()
         WHERE bpp_id = $1
           AND transaction_id <> $2
+          AND deleted_at IS NULL
         "#,
     )
     .bind(bpp_id)
@@ -208,6 +291,82 @@ pub async fn delete_stale_profiles(
     Ok(result.rows_affected())
 }
 
+/// Removes profiles soft-deleted more than `days` days ago.
+pub async fn purge_deleted_profiles(db_pool: &PgPool, days: u32) -> Result<u64, sqlx::Error> {
+    let result = query(
+        r#"
+        DELETE FROM profiles
+        WHERE deleted_at < now() - $1 * INTERVAL '1 day'
+        "#,
+    )
+    .bind(days as i32)
+    .execute(db_pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// WHERE conditions over `profiles p` shared by talent search, saved search
+/// alerts and exports. Takes $1 trade, $2 location, $3 experience and $4
+/// consent purpose, bound by [`TalentFilter::bind`]; a query's own
+/// parameters start at $5.
+macro_rules! talent_filter_sql {
+    () => {
+        r#"
+          p.beckn_structure IS NOT NULL
+          AND p.deleted_at IS NULL
+          AND ($1::text IS NULL OR
+               p.beckn_structure->'tags'->'profile'->>'role' ILIKE $1
+               OR p.beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
+               OR p.beckn_structure->'tags'->'profile'->'whatIHave'->>'nameOfLastRoleHeld' ILIKE $1)
+          AND ($2::text IS NULL OR
+               p.beckn_structure->'tags'->'profile'->>'location' ILIKE $2
+               OR p.beckn_structure->'tags'->'profile'->>'city' ILIKE $2
+               OR (p.beckn_structure->'tags'->'profile'->'locationdata'->>'city') ILIKE $2
+               OR (p.beckn_structure->'tags'->'profile'->'location'->>'city') ILIKE $2)
+          AND ($3::text IS NULL OR
+               p.beckn_structure->'tags'->'profile'->'whatIHave'->>'workExperience' ILIKE $3)
+          AND ($4::text IS NULL OR has_valid_consent(p.profile_id, $4))
+        "#
+    };
+}
+pub(crate) use talent_filter_sql;
+
+/// Trade, location and experience substring filters plus the consent check.
+#[derive(Default)]
+pub struct TalentFilter {
+    pub trade: Option<String>,
+    pub location: Option<String>,
+    pub experience: Option<String>,
+    pub consent_purpose: Option<String>,
+}
+
+impl TalentFilter {
+    /// Binds $1 to $4 of [`talent_filter_sql`].
+    pub fn bind<'q>(
+        &self,
+        query: Query<'q, Postgres, PgArguments>,
+    ) -> Query<'q, Postgres, PgArguments> {
+        let pattern = |value: &Option<String>| value.as_ref().map(|v| format!("%{v}%"));
+        query
+            .bind(pattern(&self.trade))
+            .bind(pattern(&self.location))
+            .bind(pattern(&self.experience))
+            .bind(self.consent_purpose.clone())
+    }
+}
+
+impl From<&TalentSearchParams> for TalentFilter {
+    fn from(params: &TalentSearchParams) -> Self {
+        TalentFilter {
+            trade: params.trade.clone(),
+            location: params.location.clone(),
+            experience: params.experience.clone(),
+            consent_purpose: params.consent_purpose.clone(),
+        }
+    }
+}
+
 pub struct TalentSearchParams {
     pub trade: Option<String>,
     pub location: Option<String>,
@@ -215,6 +374,8 @@ pub struct TalentSearchParams {
     pub experience: Option<String>,
     pub page: u32,
     pub limit: u32,
+    /// When set, only candidates with valid consent for this purpose match.
+    pub consent_purpose: Option<String>,
 }
 
 pub struct TalentSearchResult {
@@ -237,181 +398,190 @@ pub struct SampleCandidate {
     pub qualification: Option<String>,
     pub job_roles_interested_in: Option<String>,
     pub jobs_interested_in: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<PipelineStatus>,
+}
+
+/// A redacted copy of the `tags.profile` section of a stored beckn item.
+pub fn redacted_profile(beckn: Option<&Value>, view: &RedactionView) -> Option<Value> {
+    let mut profile = beckn
+        .and_then(|b| b.get("tags"))
+        .and_then(|t| t.get("profile"))
+        .cloned()?;
+    view.apply(&mut profile);
+    Some(profile)
 }
 
+impl SampleCandidate {
+    /// Normalizes the `tags.profile` section of a stored beckn item into the
+    /// flat candidate shape used by talent search, matching, exports and
+    /// alerts, after redacting it for whoever receives the output.
+    pub fn from_beckn(profile_id: String, beckn: Option<&Value>, view: &RedactionView) -> Self {
+        let profile = redacted_profile(beckn, view);
+        Self::from_profile(profile_id, profile.as_ref())
+    }
+
+    /// Normalizes a `tags.profile` object that has already been redacted.
+    pub fn from_profile(profile_id: String, profile: Option<&Value>) -> Self {
+        let who_i_am = profile.and_then(|p| p.get("whoIAm"));
+        let what_i_have = profile.and_then(|p| p.get("whatIHave"));
+        let what_i_want = profile.and_then(|p| p.get("whatIWant"));
+
+        let name = who_i_am
+            .and_then(|w| w.get("name"))
+            .and_then(|n| n.as_str())
+            .map(String::from);
+
+        let role = profile
+            .and_then(|p| p.get("role"))
+            .and_then(|r| r.as_str())
+            .map(String::from);
+
+        let location = profile
+            .and_then(|p| p.get("location"))
+            .and_then(|l| l.get("address"))
+            .or_else(|| {
+                profile
+                    .and_then(|p| p.get("location"))
+                    .and_then(|l| l.get("city"))
+            })
+            .or_else(|| {
+                profile
+                    .and_then(|p| p.get("locationdata"))
+                    .and_then(|l| l.get("city"))
+            })
+            .or_else(|| {
+                profile
+                    .and_then(|p| p.get("locationdata"))
+                    .and_then(|l| l.get("address"))
+            })
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let work_experience = what_i_have
+            .and_then(|w| w.get("workExperience"))
+            .and_then(|w| w.as_str())
+            .map(String::from);
+
+        let work_experience_years = what_i_have
+            .and_then(|w| w.get("workExperienceYears"))
+            .and_then(|w| w.as_str())
+            .map(String::from);
+
+        let last_role_held = what_i_have
+            .and_then(|w| w.get("nameOfLastRoleHeld"))
+            .and_then(|n| n.as_str())
+            .map(String::from);
+
+        let qualification = what_i_have
+            .and_then(|w| w.get("highestQualificationOrSkill"))
+            .and_then(|h| h.get("category"))
+            .and_then(|c| c.as_str())
+            .map(String::from);
+
+        let job_roles_interested_in = what_i_want
+            .and_then(|w| w.get("nameOfJobRolesInterestedIn"))
+            .and_then(|n| n.as_str())
+            .map(String::from);
+
+        let jobs_interested_in = what_i_want
+            .and_then(|w| w.get("natureOfJobsInterestedIn"))
+            .and_then(|n| n.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            });
+
+        SampleCandidate {
+            profile_id,
+            name,
+            role,
+            location,
+            work_experience,
+            work_experience_years,
+            last_role_held,
+            qualification,
+            job_roles_interested_in,
+            jobs_interested_in,
+            pipeline: None,
+        }
+    }
+}
+
+/// Profiles passing `filter`, as searched and exported.
+pub async fn count_talent_matches(
+    db_pool: &PgPool,
+    filter: &TalentFilter,
+) -> Result<i64, sqlx::Error> {
+    filter
+        .bind(query(concat!(
+            "SELECT COUNT(*) AS count FROM profiles p WHERE",
+            talent_filter_sql!()
+        )))
+        .fetch_one(db_pool)
+        .await?
+        .try_get("count")
+}
+
+/// Candidates are redacted with `view` and show their pipeline stage on
+/// `owner`'s shortlists.
 pub async fn search_talent(
     db_pool: &PgPool,
     params: TalentSearchParams,
+    view: &RedactionView,
+    owner: &str,
 ) -> Result<TalentSearchResult, sqlx::Error> {
     let page = params.page.max(1);
     let limit = params.limit.clamp(1, 100);
     let offset = (page - 1) * limit;
 
-    let trade_pattern = params.trade.as_ref().map(|t| format!("%{}%", t));
-    let location_pattern = params.location.as_ref().map(|l| format!("%{}%", l));
-    let experience_pattern = params.experience.as_ref().map(|e| format!("%{}%", e));
+    let filter = TalentFilter::from(&params);
 
     let candidate_count: i64 = query_scalar(
         r#"
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
+          AND ($1::text IS NULL OR has_valid_consent(profile_id, $1))
         "#,
     )
+    .bind(&params.consent_purpose)
     .fetch_one(db_pool)
     .await?;
 
-    let matched_count: i64 = query_scalar(
-        r#"
-        SELECT COUNT(*) 
-        FROM profiles
-        WHERE beckn_structure IS NOT NULL
-          AND ($1::text IS NULL OR 
-               beckn_structure->'tags'->'profile'->>'role' ILIKE $1
-               OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
-               OR beckn_structure->'tags'->'profile'->'whatIHave'->>'nameOfLastRoleHeld' ILIKE $1)
-          AND ($2::text IS NULL OR 
-               beckn_structure->'tags'->'profile'->>'location' ILIKE $2
-               OR beckn_structure->'tags'->'profile'->>'city' ILIKE $2
-               OR (beckn_structure->'tags'->'profile'->'locationdata'->>'city') ILIKE $2
-               OR (beckn_structure->'tags'->'profile'->'location'->>'city') ILIKE $2)
-          AND ($3::text IS NULL OR 
-               beckn_structure->'tags'->'profile'->'whatIHave'->>'workExperience' ILIKE $3)
-        "#,
-    )
-    .bind(&trade_pattern)
-    .bind(&location_pattern)
-    .bind(&experience_pattern)
-    .fetch_one(db_pool)
-    .await?;
+    let matched_count = count_talent_matches(db_pool, &filter).await?;
 
-    let rows = query(
-        r#"
-        SELECT
-            id,
-            profile_id,
-            beckn_structure,
-            updated_at
-        FROM profiles
-        WHERE beckn_structure IS NOT NULL
-          AND ($1::text IS NULL OR 
-               beckn_structure->'tags'->'profile'->>'role' ILIKE $1
-               OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
-               OR beckn_structure->'tags'->'profile'->'whatIHave'->>'nameOfLastRoleHeld' ILIKE $1)
-          AND ($2::text IS NULL OR 
-               beckn_structure->'tags'->'profile'->>'location' ILIKE $2
-               OR beckn_structure->'tags'->'profile'->>'city' ILIKE $2
-               OR (beckn_structure->'tags'->'profile'->'locationdata'->>'city') ILIKE $2
-               OR (beckn_structure->'tags'->'profile'->'location'->>'city') ILIKE $2)
-          AND ($3::text IS NULL OR 
-               beckn_structure->'tags'->'profile'->'whatIHave'->>'workExperience' ILIKE $3)
-        ORDER BY updated_at DESC, profile_id DESC
-        LIMIT $4
-        OFFSET $5
-        "#,
-    )
-    .bind(&trade_pattern)
-    .bind(&location_pattern)
-    .bind(&experience_pattern)
-    .bind(limit as i64)
-    .bind(offset as i64)
-    .fetch_all(db_pool)
-    .await?;
+    let rows = filter
+        .bind(query(concat!(
+            "SELECT p.id, p.profile_id, p.beckn_structure, p.updated_at FROM profiles p WHERE",
+            talent_filter_sql!(),
+            "ORDER BY p.updated_at DESC, p.profile_id DESC LIMIT $5 OFFSET $6"
+        )))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(db_pool)
+        .await?;
 
-    let results = rows
+    let mut results: Vec<SampleCandidate> = rows
         .into_iter()
         .map(|r| {
             let beckn: Option<Value> = r.try_get("beckn_structure").ok();
-            let profile = beckn
-                .as_ref()
-                .and_then(|b| b.get("tags"))
-                .and_then(|t| t.get("profile"));
-
-            let who_i_am = profile.and_then(|p| p.get("whoIAm"));
-            let what_i_have = profile.and_then(|p| p.get("whatIHave"));
-            let what_i_want = profile.and_then(|p| p.get("whatIWant"));
-
-            let name = who_i_am
-                .and_then(|w| w.get("name"))
-                .and_then(|n| n.as_str())
-                .map(String::from);
-
-            let role = profile
-                .and_then(|p| p.get("role"))
-                .and_then(|r| r.as_str())
-                .map(String::from);
-
-            let location = profile
-                .and_then(|p| p.get("location"))
-                .and_then(|l| l.get("address"))
-                .or_else(|| {
-                    profile
-                        .and_then(|p| p.get("location"))
-                        .and_then(|l| l.get("city"))
-                })
-                .or_else(|| {
-                    profile
-                        .and_then(|p| p.get("locationdata"))
-                        .and_then(|l| l.get("city"))
-                })
-                .or_else(|| {
-                    profile
-                        .and_then(|p| p.get("locationdata"))
-                        .and_then(|l| l.get("address"))
-                })
-                .and_then(|v| v.as_str())
-                .map(String::from);
-
-            let work_experience = what_i_have
-                .and_then(|w| w.get("workExperience"))
-                .and_then(|w| w.as_str())
-                .map(String::from);
-
-            let work_experience_years = what_i_have
-                .and_then(|w| w.get("workExperienceYears"))
-                .and_then(|w| w.as_str())
-                .map(String::from);
-
-            let last_role_held = what_i_have
-                .and_then(|w| w.get("nameOfLastRoleHeld"))
-                .and_then(|n| n.as_str())
-                .map(String::from);
-
-            let qualification = what_i_have
-                .and_then(|w| w.get("highestQualificationOrSkill"))
-                .and_then(|h| h.get("category"))
-                .and_then(|c| c.as_str())
-                .map(String::from);
-
-            let job_roles_interested_in = what_i_want
-                .and_then(|w| w.get("nameOfJobRolesInterestedIn"))
-                .and_then(|n| n.as_str())
-                .map(String::from);
-
-            let jobs_interested_in = what_i_want
-                .and_then(|w| w.get("natureOfJobsInterestedIn"))
-                .and_then(|n| n.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(String::from))
-                        .collect()
-                });
-
-            SampleCandidate {
-                profile_id: r.try_get::<String, _>("profile_id").unwrap_or_default(),
-                name,
-                role,
-                location,
-                work_experience,
-                work_experience_years,
-                last_role_held,
-                qualification,
-                job_roles_interested_in,
-                jobs_interested_in,
-            }
+            SampleCandidate::from_beckn(
+                r.try_get::<String, _>("profile_id").unwrap_or_default(),
+                beckn.as_ref(),
+                view,
+            )
         })
         .collect();
 
+    let profile_ids: Vec<&str> = results.iter().map(|c| c.profile_id.as_str()).collect();
+    let mut statuses = get_pipeline_statuses(db_pool, owner, &profile_ids).await?;
+    for candidate in results.iter_mut() {
+        candidate.pipeline = statuses.remove(&candidate.profile_id);
+    }
+
     Ok(TalentSearchResult {
         candidate_count,
         matched_count,
@@ -451,14 +621,15 @@ pub async fn get_market_insights(
     db_pool: &PgPool,
     params: MarketInsightsParams,
 ) -> Result<MarketInsightsResult, sqlx::Error> {
-    let role_pattern = params.role.as_ref().map(|r| format!("%{}%", r));
-    let location_pattern = params.location.as_ref().map(|l| format!("%{}%", l));
+    let role_pattern = params.role.as_ref().map(|r| format!("%{r}%"));
+    let location_pattern = params.location.as_ref().map(|l| format!("%{l}%"));
 
     let total_candidates: i64 = query_scalar(
         r#"
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
         "#,
     )
     .fetch_one(db_pool)
@@ -469,6 +640,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -500,6 +672,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -524,6 +697,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -546,6 +720,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -568,6 +743,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -590,6 +766,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -618,6 +795,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -640,6 +818,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -662,6 +841,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -684,6 +864,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -706,6 +887,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -728,6 +910,7 @@ pub async fn get_market_insights(
         SELECT COUNT(*) 
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -759,6 +942,7 @@ pub async fn get_market_insights(
             COUNT(*) as count
         FROM profiles
         WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
           AND ($1::text IS NULL OR 
                beckn_structure->'tags'->'profile'->>'role' ILIKE $1
                OR beckn_structure->'tags'->'profile'->'whatIWant'->>'nameOfJobRolesInterestedIn' ILIKE $1
@@ -807,17 +991,24 @@ pub async fn get_market_insights(
 pub struct CandidateDetails {
     pub profile_id: String,
     pub profile: Value,
+    pub pipeline: Option<PipelineStatus>,
 }
 
+/// Loads a candidate's profile and its stage on `owner`'s shortlists.
+/// Encrypted fields are decrypted with `keyring` when one is given and
+/// masked otherwise.
 pub async fn get_candidate_by_id(
     db_pool: &PgPool,
     profile_id: &str,
+    owner: &str,
+    keyring: Option<&Keyring>,
 ) -> Result<Option<CandidateDetails>, sqlx::Error> {
     let row = query(
         r#"
         SELECT profile_id, beckn_structure
         FROM profiles
         WHERE profile_id = $1
+          AND deleted_at IS NULL
         "#,
     )
     .bind(profile_id)
@@ -827,18 +1018,200 @@ pub async fn get_candidate_by_id(
     match row {
         Some(r) => {
             let beckn_structure: Option<Value> = r.try_get("beckn_structure").ok().flatten();
-            let profile = beckn_structure
+            let mut profile = beckn_structure
                 .as_ref()
                 .and_then(|b| b.get("tags"))
                 .and_then(|t| t.get("profile"))
                 .cloned()
                 .unwrap_or(Value::Null);
 
+            let profile_id = r.try_get::<String, _>("profile_id").unwrap_or_default();
+            match keyring.map(|k| k.decrypt_all(&mut profile, &profile_id, "tags.profile")) {
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    tracing::error!("Decrypting profile {} failed: {:?}", profile_id, e);
+                    mask_envelopes(&mut profile);
+                }
+                None => {
+                    mask_envelopes(&mut profile);
+                }
+            }
+            let pipeline = get_pipeline_statuses(db_pool, owner, &[profile_id.as_str()])
+                .await?
+                .remove(&profile_id);
+
             Ok(Some(CandidateDetails {
-                profile_id: r.try_get::<String, _>("profile_id").unwrap_or_default(),
+                profile_id,
                 profile,
+                pipeline,
             }))
         }
         None => Ok(None),
     }
 }
+
+pub struct StoredProfile {
+    pub id: uuid::Uuid,
+    pub profile_id: String,
+    pub beckn_structure: Value,
+    pub hash: String,
+}
+
+/// Keyset-paginated scan over stored profiles, ordered by row id.
+pub async fn fetch_profile_batch(
+    db_pool: &PgPool,
+    after: Option<uuid::Uuid>,
+    limit: u32,
+) -> Result<Vec<StoredProfile>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT id, profile_id, beckn_structure, hash
+        FROM profiles
+        WHERE beckn_structure IS NOT NULL
+          AND ($1::uuid IS NULL OR id > $1)
+        ORDER BY id
+        LIMIT $2
+        "#,
+    )
+    .bind(after)
+    .bind(limit as i64)
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|r| {
+            Ok(StoredProfile {
+                id: r.try_get("id")?,
+                profile_id: r.try_get("profile_id")?,
+                beckn_structure: r.try_get("beckn_structure")?,
+                hash: r.try_get("hash")?,
+            })
+        })
+        .collect()
+}
+
+/// Rewrites the stored structure without touching `hash`, so re-encryption
+/// is not mistaken for a content change by the sync. Returns `false` when
+/// the row no longer has `hash`, i.e. a sync replaced it after it was read.
+pub async fn update_beckn_structure(
+    db_pool: &PgPool,
+    id: uuid::Uuid,
+    hash: &str,
+    beckn_structure: &Value,
+) -> Result<bool, sqlx::Error> {
+    let result = query("UPDATE profiles SET beckn_structure = $2 WHERE id = $1 AND hash = $3")
+        .bind(id)
+        .bind(beckn_structure)
+        .bind(hash)
+        .execute(db_pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub struct MatchPoolRow {
+    pub profile_id: String,
+    pub beckn_structure: Value,
+}
+
+/// Active profiles the matcher may rank, most recently updated first, at
+/// most `pool_limit` of them. Only consent and soft deletion exclude a
+/// profile here; every job criterion is left to the scorers, so a miss on
+/// one never hides a strong match on the others.
+pub async fn fetch_match_pool(
+    db_pool: &PgPool,
+    consent_purpose: Option<&str>,
+    pool_limit: u32,
+) -> Result<Vec<MatchPoolRow>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT profile_id, beckn_structure
+        FROM profiles
+        WHERE beckn_structure IS NOT NULL
+          AND deleted_at IS NULL
+          AND ($2::text IS NULL OR has_valid_consent(profile_id, $2))
+        ORDER BY updated_at DESC, profile_id
+        LIMIT $1
+        "#,
+    )
+    .bind(pool_limit as i64)
+    .bind(consent_purpose)
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| {
+            Some(MatchPoolRow {
+                profile_id: r.try_get::<String, _>("profile_id").ok()?,
+                beckn_structure: r.try_get::<Option<Value>, _>("beckn_structure").ok()??,
+            })
+        })
+        .collect())
+}
+
+pub struct ExportRow {
+    pub transaction_id: String,
+    pub bpp_id: String,
+    pub bpp_uri: String,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub candidate: SampleCandidate,
+}
+
+pub fn stream_export_rows<'a>(
+    db_pool: &'a PgPool,
+    filter: TalentFilter,
+    view: RedactionView,
+    max_rows: u64,
+) -> impl Stream<Item = Result<ExportRow, sqlx::Error>> + Send + 'a {
+    filter
+        .bind(query(concat!(
+            "SELECT p.profile_id, p.beckn_structure, p.transaction_id, p.bpp_id, p.bpp_uri, p.updated_at FROM profiles p WHERE",
+            talent_filter_sql!(),
+            "ORDER BY p.updated_at DESC, p.profile_id DESC LIMIT $5"
+        )))
+        .bind(max_rows as i64)
+    .fetch(db_pool)
+    .map(move |row| {
+        let r = row?;
+        let beckn: Option<Value> = r.try_get("beckn_structure").ok();
+        Ok(ExportRow {
+            transaction_id: r.try_get("transaction_id").unwrap_or_default(),
+            bpp_id: r.try_get("bpp_id").unwrap_or_default(),
+            bpp_uri: r.try_get("bpp_uri").unwrap_or_default(),
+            updated_at: r.try_get("updated_at").ok(),
+            candidate: SampleCandidate::from_beckn(
+                r.try_get::<String, _>("profile_id").unwrap_or_default(),
+                beckn.as_ref(),
+                &view,
+            ),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedactionConfig;
+    use crate::models::auth::CallerRole;
+    use serde_json::json;
+
+    #[test]
+    fn from_beckn_applies_the_callers_redaction() {
+        let beckn = json!({
+            "tags": { "profile": {
+                "whoIAm": { "name": "Asha" },
+                "location": { "address": "12 MG Road", "city": "Pune" }
+            } }
+        });
+        let policy = RedactionConfig::default();
+
+        let standard = RedactionView::new(&policy, CallerRole::Standard);
+        let candidate = SampleCandidate::from_beckn("p1".into(), Some(&beckn), &standard);
+        assert_eq!(candidate.location.as_deref(), Some("Pune"));
+
+        let privileged = RedactionView::new(&policy, CallerRole::Privileged);
+        let candidate = SampleCandidate::from_beckn("p1".into(), Some(&beckn), &privileged);
+        assert_eq!(candidate.location.as_deref(), Some("12 MG Road"));
+        assert_eq!(candidate.name.as_deref(), Some("Asha"));
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{query, query_scalar, PgPool, Row};
+
+#[derive(Debug, Serialize)]
+pub struct ProfileUnlock {
+    pub profile_id: String,
+    pub caller_id: String,
+    pub reason: String,
+    pub unlocked_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub async fn create_unlock(
+    db_pool: &PgPool,
+    profile_id: &str,
+    caller_id: &str,
+    reason: &str,
+    ttl_seconds: u64,
+) -> Result<ProfileUnlock, sqlx::Error> {
+    let r = query(
+        r#"
+        INSERT INTO profile_unlocks (profile_id, caller_id, reason, expires_at)
+        VALUES ($1, $2, $3, now() + $4 * INTERVAL '1 second')
+        RETURNING profile_id, caller_id, reason, unlocked_at, expires_at
+        "#,
+    )
+    .bind(profile_id)
+    .bind(caller_id)
+    .bind(reason)
+    .bind(ttl_seconds as i64)
+    .fetch_one(db_pool)
+    .await?;
+
+    Ok(ProfileUnlock {
+        profile_id: r.try_get("profile_id")?,
+        caller_id: r.try_get("caller_id")?,
+        reason: r.try_get("reason")?,
+        unlocked_at: r.try_get("unlocked_at")?,
+        expires_at: r.try_get("expires_at")?,
+    })
+}
+
+pub async fn has_active_unlock(
+    db_pool: &PgPool,
+    profile_id: &str,
+    caller_id: &str,
+) -> Result<bool, sqlx::Error> {
+    query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM profile_unlocks
+            WHERE profile_id = $1
+              AND caller_id = $2
+              AND expires_at > now()
+        )
+        "#,
+    )
+    .bind(profile_id)
+    .bind(caller_id)
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn list_unlocks(
+    db_pool: &PgPool,
+    profile_id: &str,
+) -> Result<Vec<ProfileUnlock>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT profile_id, caller_id, reason, unlocked_at, expires_at
+        FROM profile_unlocks
+        WHERE profile_id = $1
+        ORDER BY unlocked_at DESC
+        "#,
+    )
+    .bind(profile_id)
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|r| {
+            Ok(ProfileUnlock {
+                profile_id: r.try_get("profile_id")?,
+                caller_id: r.try_get("caller_id")?,
+                reason: r.try_get("reason")?,
+                unlocked_at: r.try_get("unlocked_at")?,
+                expires_at: r.try_get("expires_at")?,
+            })
+        })
+        .collect()
+}
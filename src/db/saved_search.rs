@@ -0,0 +1,263 @@
+use crate::db::profile::{talent_filter_sql, SampleCandidate, TalentFilter};
+use crate::utils::redaction::RedactionView;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{postgres::PgRow, query, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct SavedSearch {
+    pub id: Uuid,
+    pub owner: String,
+    pub name: String,
+    pub query: Value,
+    pub schedule_seconds: i64,
+    pub webhook_url: Option<String>,
+    pub active: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct NewSavedSearch {
+    pub owner: String,
+    pub name: String,
+    pub query: Value,
+    pub schedule_seconds: i64,
+    pub webhook_url: Option<String>,
+    pub active: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SavedSearchMatch {
+    pub change: &'static str,
+    #[serde(skip)]
+    pub hash: String,
+    #[serde(flatten)]
+    pub candidate: SampleCandidate,
+}
+
+fn map_saved_search(r: PgRow) -> Result<SavedSearch, sqlx::Error> {
+    Ok(SavedSearch {
+        id: r.try_get("id")?,
+        owner: r.try_get("owner")?,
+        name: r.try_get("name")?,
+        query: r.try_get("query")?,
+        schedule_seconds: r.try_get("schedule_seconds")?,
+        webhook_url: r.try_get("webhook_url")?,
+        active: r.try_get("active")?,
+        last_run_at: r.try_get("last_run_at")?,
+        created_at: r.try_get("created_at")?,
+        updated_at: r.try_get("updated_at")?,
+    })
+}
+
+pub async fn create_saved_search(
+    db_pool: &PgPool,
+    new: &NewSavedSearch,
+) -> Result<SavedSearch, sqlx::Error> {
+    let row = query(
+        r#"
+        INSERT INTO saved_searches (owner, name, query, schedule_seconds, webhook_url, active)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(&new.owner)
+    .bind(&new.name)
+    .bind(&new.query)
+    .bind(new.schedule_seconds)
+    .bind(&new.webhook_url)
+    .bind(new.active)
+    .fetch_one(db_pool)
+    .await?;
+
+    map_saved_search(row)
+}
+
+/// Updates a saved search belonging to `new.owner`; `None` when there is
+/// no such search or it belongs to someone else.
+pub async fn update_saved_search(
+    db_pool: &PgPool,
+    id: Uuid,
+    new: &NewSavedSearch,
+) -> Result<Option<SavedSearch>, sqlx::Error> {
+    let row = query(
+        r#"
+        UPDATE saved_searches
+        SET name = $3,
+            query = $4,
+            schedule_seconds = $5,
+            webhook_url = $6,
+            active = $7,
+            updated_at = now()
+        WHERE id = $1 AND owner = $2
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&new.owner)
+    .bind(&new.name)
+    .bind(&new.query)
+    .bind(new.schedule_seconds)
+    .bind(&new.webhook_url)
+    .bind(new.active)
+    .fetch_optional(db_pool)
+    .await?;
+
+    row.map(map_saved_search).transpose()
+}
+
+pub async fn get_saved_search(
+    db_pool: &PgPool,
+    id: Uuid,
+    owner: &str,
+) -> Result<Option<SavedSearch>, sqlx::Error> {
+    let row = query("SELECT * FROM saved_searches WHERE id = $1 AND owner = $2")
+        .bind(id)
+        .bind(owner)
+        .fetch_optional(db_pool)
+        .await?;
+
+    row.map(map_saved_search).transpose()
+}
+
+pub async fn list_saved_searches(
+    db_pool: &PgPool,
+    owner: &str,
+) -> Result<Vec<SavedSearch>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT *
+        FROM saved_searches
+        WHERE owner = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(owner)
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter().map(map_saved_search).collect()
+}
+
+pub async fn delete_saved_search(
+    db_pool: &PgPool,
+    id: Uuid,
+    owner: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = query("DELETE FROM saved_searches WHERE id = $1 AND owner = $2")
+        .bind(id)
+        .bind(owner)
+        .execute(db_pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn fetch_due_saved_searches(db_pool: &PgPool) -> Result<Vec<SavedSearch>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT *
+        FROM saved_searches
+        WHERE active
+          AND (last_run_at IS NULL
+               OR last_run_at + schedule_seconds * INTERVAL '1 second' <= now())
+        ORDER BY last_run_at NULLS FIRST
+        "#,
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter().map(map_saved_search).collect()
+}
+
+pub async fn find_new_matches(
+    db_pool: &PgPool,
+    saved_search_id: Uuid,
+    filter: &TalentFilter,
+    view: &RedactionView,
+    limit: u32,
+) -> Result<Vec<SavedSearchMatch>, sqlx::Error> {
+    let rows = filter
+        .bind(query(concat!(
+            r#"
+            SELECT
+                p.profile_id,
+                p.beckn_structure,
+                p.hash,
+                m.hash AS previous_hash
+            FROM profiles p
+            LEFT JOIN saved_search_matches m
+              ON m.saved_search_id = $5 AND m.profile_id = p.profile_id
+            WHERE (m.hash IS NULL OR m.hash <> p.hash)
+              AND"#,
+            talent_filter_sql!(),
+            "ORDER BY p.profile_id LIMIT $6"
+        )))
+        .bind(saved_search_id)
+        .bind(limit as i64)
+        .fetch_all(db_pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let beckn: Option<Value> = r.try_get("beckn_structure").ok();
+            let previous_hash: Option<String> = r.try_get("previous_hash").ok().flatten();
+            SavedSearchMatch {
+                change: if previous_hash.is_some() {
+                    "changed"
+                } else {
+                    "new"
+                },
+                hash: r.try_get("hash").unwrap_or_default(),
+                candidate: SampleCandidate::from_beckn(
+                    r.try_get::<String, _>("profile_id").unwrap_or_default(),
+                    beckn.as_ref(),
+                    view,
+                ),
+            }
+        })
+        .collect())
+}
+
+pub async fn record_matches(
+    db_pool: &PgPool,
+    saved_search_id: Uuid,
+    matches: &[SavedSearchMatch],
+) -> Result<(), sqlx::Error> {
+    let profile_ids: Vec<&str> = matches
+        .iter()
+        .map(|m| m.candidate.profile_id.as_str())
+        .collect();
+    let hashes: Vec<&str> = matches.iter().map(|m| m.hash.as_str()).collect();
+
+    query(
+        r#"
+        INSERT INTO saved_search_matches (saved_search_id, profile_id, hash)
+        SELECT $1, profile_id, hash
+        FROM UNNEST($2::text[], $3::text[]) AS t(profile_id, hash)
+        ON CONFLICT (saved_search_id, profile_id) DO UPDATE
+        SET hash = EXCLUDED.hash,
+            matched_at = now()
+        "#,
+    )
+    .bind(saved_search_id)
+    .bind(&profile_ids)
+    .bind(&hashes)
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn mark_saved_search_run(db_pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+    query("UPDATE saved_searches SET last_run_at = now() WHERE id = $1")
+        .bind(id)
+        .execute(db_pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,365 @@
+use crate::models::shortlist::PipelineStage;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{postgres::PgRow, query, PgPool, Row};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct Shortlist {
+    pub id: Uuid,
+    pub owner: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShortlistCandidate {
+    pub id: Uuid,
+    pub shortlist_id: Uuid,
+    pub profile_id: String,
+    pub stage: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PipelineEvent {
+    pub stage: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PipelineStatus {
+    pub shortlist_id: Uuid,
+    pub shortlist_name: String,
+    pub stage: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn map_shortlist(r: PgRow) -> Result<Shortlist, sqlx::Error> {
+    Ok(Shortlist {
+        id: r.try_get("id")?,
+        owner: r.try_get("owner")?,
+        name: r.try_get("name")?,
+        description: r.try_get("description")?,
+        created_at: r.try_get("created_at")?,
+        updated_at: r.try_get("updated_at")?,
+    })
+}
+
+fn map_candidate(r: PgRow) -> Result<ShortlistCandidate, sqlx::Error> {
+    Ok(ShortlistCandidate {
+        id: r.try_get("id")?,
+        shortlist_id: r.try_get("shortlist_id")?,
+        profile_id: r.try_get("profile_id")?,
+        stage: r.try_get("stage")?,
+        notes: r.try_get("notes")?,
+        created_at: r.try_get("created_at")?,
+        updated_at: r.try_get("updated_at")?,
+    })
+}
+
+pub async fn create_shortlist(
+    db_pool: &PgPool,
+    owner: &str,
+    name: &str,
+    description: Option<&str>,
+) -> Result<Shortlist, sqlx::Error> {
+    let row = query(
+        r#"
+        INSERT INTO shortlists (owner, name, description)
+        VALUES ($1, $2, $3)
+        RETURNING *
+        "#,
+    )
+    .bind(owner)
+    .bind(name)
+    .bind(description)
+    .fetch_one(db_pool)
+    .await?;
+
+    map_shortlist(row)
+}
+
+/// Updates a shortlist belonging to `owner`; `None` when there is no such
+/// shortlist or it belongs to someone else.
+pub async fn update_shortlist(
+    db_pool: &PgPool,
+    id: Uuid,
+    owner: &str,
+    name: &str,
+    description: Option<&str>,
+) -> Result<Option<Shortlist>, sqlx::Error> {
+    let row = query(
+        r#"
+        UPDATE shortlists
+        SET name = $3,
+            description = $4,
+            updated_at = now()
+        WHERE id = $1 AND owner = $2
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(owner)
+    .bind(name)
+    .bind(description)
+    .fetch_optional(db_pool)
+    .await?;
+
+    row.map(map_shortlist).transpose()
+}
+
+pub async fn get_shortlist(
+    db_pool: &PgPool,
+    id: Uuid,
+    owner: &str,
+) -> Result<Option<Shortlist>, sqlx::Error> {
+    let row = query("SELECT * FROM shortlists WHERE id = $1 AND owner = $2")
+        .bind(id)
+        .bind(owner)
+        .fetch_optional(db_pool)
+        .await?;
+
+    row.map(map_shortlist).transpose()
+}
+
+pub async fn list_shortlists(db_pool: &PgPool, owner: &str) -> Result<Vec<Shortlist>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT *
+        FROM shortlists
+        WHERE owner = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(owner)
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter().map(map_shortlist).collect()
+}
+
+pub async fn delete_shortlist(
+    db_pool: &PgPool,
+    id: Uuid,
+    owner: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = query("DELETE FROM shortlists WHERE id = $1 AND owner = $2")
+        .bind(id)
+        .bind(owner)
+        .execute(db_pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn list_shortlist_candidates(
+    db_pool: &PgPool,
+    shortlist_id: Uuid,
+) -> Result<Vec<ShortlistCandidate>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT *
+        FROM shortlist_candidates
+        WHERE shortlist_id = $1
+        ORDER BY updated_at DESC
+        "#,
+    )
+    .bind(shortlist_id)
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter().map(map_candidate).collect()
+}
+
+pub async fn add_shortlist_candidate(
+    db_pool: &PgPool,
+    shortlist_id: Uuid,
+    profile_id: &str,
+    stage: PipelineStage,
+    notes: Option<&str>,
+) -> Result<ShortlistCandidate, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+
+    let row = query(
+        r#"
+        INSERT INTO shortlist_candidates (shortlist_id, profile_id, stage, notes)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(shortlist_id)
+    .bind(profile_id)
+    .bind(stage.as_str())
+    .bind(notes)
+    .fetch_one(&mut *tx)
+    .await?;
+    let candidate = map_candidate(row)?;
+
+    query(
+        r#"
+        INSERT INTO pipeline_events (shortlist_candidate_id, stage, notes)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(candidate.id)
+    .bind(&candidate.stage)
+    .bind(notes)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(candidate)
+}
+
+pub async fn update_shortlist_candidate(
+    db_pool: &PgPool,
+    shortlist_id: Uuid,
+    profile_id: &str,
+    stage: Option<PipelineStage>,
+    notes: Option<&str>,
+) -> Result<Option<ShortlistCandidate>, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+
+    let row = query(
+        r#"
+        UPDATE shortlist_candidates
+        SET stage = COALESCE($3, stage),
+            notes = COALESCE($4, notes),
+            updated_at = now()
+        WHERE shortlist_id = $1
+          AND profile_id = $2
+        RETURNING *
+        "#,
+    )
+    .bind(shortlist_id)
+    .bind(profile_id)
+    .bind(stage.map(|s| s.as_str()))
+    .bind(notes)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let candidate = map_candidate(row)?;
+
+    query(
+        r#"
+        INSERT INTO pipeline_events (shortlist_candidate_id, stage, notes)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(candidate.id)
+    .bind(&candidate.stage)
+    .bind(notes)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(candidate))
+}
+
+pub async fn remove_shortlist_candidate(
+    db_pool: &PgPool,
+    shortlist_id: Uuid,
+    profile_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = query(
+        r#"
+        DELETE FROM shortlist_candidates
+        WHERE shortlist_id = $1
+          AND profile_id = $2
+        "#,
+    )
+    .bind(shortlist_id)
+    .bind(profile_id)
+    .execute(db_pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_candidate_history(
+    db_pool: &PgPool,
+    shortlist_id: Uuid,
+    profile_id: &str,
+) -> Result<Vec<PipelineEvent>, sqlx::Error> {
+    let rows = query(
+        r#"
+        SELECT e.stage, e.notes, e.created_at
+        FROM pipeline_events e
+        JOIN shortlist_candidates c ON c.id = e.shortlist_candidate_id
+        WHERE c.shortlist_id = $1
+          AND c.profile_id = $2
+        ORDER BY e.created_at, e.id
+        "#,
+    )
+    .bind(shortlist_id)
+    .bind(profile_id)
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|r| {
+            Ok(PipelineEvent {
+                stage: r.try_get("stage")?,
+                notes: r.try_get("notes")?,
+                created_at: r.try_get("created_at")?,
+            })
+        })
+        .collect()
+}
+
+/// The latest stage of each profile across `owner`'s shortlists.
+pub async fn get_pipeline_statuses(
+    db_pool: &PgPool,
+    owner: &str,
+    profile_ids: &[&str],
+) -> Result<HashMap<String, PipelineStatus>, sqlx::Error> {
+    if profile_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = query(
+        r#"
+        SELECT DISTINCT ON (c.profile_id)
+            c.profile_id,
+            c.shortlist_id,
+            s.name AS shortlist_name,
+            c.stage,
+            c.updated_at
+        FROM shortlist_candidates c
+        JOIN shortlists s ON s.id = c.shortlist_id
+        WHERE c.profile_id = ANY($1)
+          AND s.owner = $2
+        ORDER BY c.profile_id, c.updated_at DESC
+        "#,
+    )
+    .bind(profile_ids)
+    .bind(owner)
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|r| {
+            Ok((
+                r.try_get("profile_id")?,
+                PipelineStatus {
+                    shortlist_id: r.try_get("shortlist_id")?,
+                    shortlist_name: r.try_get("shortlist_name")?,
+                    stage: r.try_get("stage")?,
+                    updated_at: r.try_get("updated_at")?,
+                },
+            ))
+        })
+        .collect()
+}
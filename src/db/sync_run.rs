@@ -0,0 +1,470 @@
+use crate::db::profile::StoreCounts;
+use crate::models::sync::{BppSyncProgress, SyncRun, SyncRunStatus, SyncTrigger};
+use chrono::Utc;
+use sqlx::{postgres::PgRow, query, query_scalar, PgExecutor, PgPool, Row};
+
+/// How long on_search pages keep arriving for a run; matches the expiry of
+/// the `pagination:*` keys in `handle_on_search`.
+pub const SYNC_WINDOW_SECONDS: i64 = 1800;
+
+/// Advisory lock key held while a run is checked for overlaps and inserted.
+const SYNC_START_LOCK: i64 = 0x73796e635f72756e;
+
+/// One on_search page as it was stored.
+pub struct SyncPage<'a> {
+    pub transaction_id: &'a str,
+    pub bpp_id: &'a str,
+    pub page: i32,
+    pub pages_expected: Option<i32>,
+    pub items: i32,
+    pub counts: StoreCounts,
+}
+
+fn map_bpp(r: &PgRow) -> Result<BppSyncProgress, sqlx::Error> {
+    Ok(BppSyncProgress {
+        bpp_id: r.try_get("bpp_id")?,
+        pages_expected: r.try_get("pages_expected")?,
+        pages_received: r.try_get::<Vec<i32>, _>("pages_received")?.len() as i32,
+        items_received: r.try_get("items_received")?,
+        inserted: r.try_get("inserted")?,
+        updated: r.try_get("updated")?,
+        unchanged: r.try_get("unchanged")?,
+        stale_deleted: r.try_get("stale_deleted")?,
+        errors: r.try_get("errors")?,
+        started_at: r.try_get("started_at")?,
+        finished_at: r.try_get("finished_at")?,
+    })
+}
+
+fn derive_status(run: &SyncRun) -> SyncRunStatus {
+    if run.cancelled_at.is_some() {
+        return SyncRunStatus::Cancelled;
+    }
+    if run.failure_reason.is_some() && run.bpps.is_empty() {
+        return SyncRunStatus::Failed;
+    }
+    let finished = |bpp_id: &str| {
+        run.bpps
+            .iter()
+            .any(|b| b.bpp_id == bpp_id && b.finished_at.is_some())
+    };
+    let done = !run.bpps.is_empty()
+        && run.bpps.iter().all(|b| b.finished_at.is_some())
+        && run.expected_bpps.iter().all(|id| finished(id));
+    if run.finished_at.is_some() || (done && !run.expected_bpps.is_empty()) {
+        return SyncRunStatus::Completed;
+    }
+    // Without an expected set, e.g. the first sync, only the window tells
+    // whether more BPPs are still going to answer.
+    let expired = run
+        .started_at
+        .is_some_and(|started| (Utc::now() - started).num_seconds() > SYNC_WINDOW_SECONDS);
+    match (expired, done) {
+        (true, true) => SyncRunStatus::Completed,
+        (true, false) => SyncRunStatus::Incomplete,
+        (false, _) => SyncRunStatus::Running,
+    }
+}
+
+/// Builds a run from its `sync_runs` row, if one was written, and its BPPs.
+fn build_run(
+    transaction_id: String,
+    row: Option<&PgRow>,
+    bpps: Vec<BppSyncProgress>,
+) -> Result<SyncRun, sqlx::Error> {
+    let mut run = SyncRun {
+        transaction_id,
+        trigger: None,
+        bpp_id: None,
+        expected_bpps: Vec::new(),
+        started_at: bpps.iter().map(|b| b.started_at).min(),
+        finished_at: None,
+        cancelled_at: None,
+        failure_reason: None,
+        status: SyncRunStatus::Running,
+        bpps,
+    };
+    if let Some(r) = row {
+        run.trigger = SyncTrigger::parse(r.try_get("trigger")?);
+        run.bpp_id = r.try_get("bpp_id")?;
+        run.expected_bpps = r.try_get("expected_bpps")?;
+        run.started_at = Some(r.try_get("started_at")?);
+        run.finished_at = r.try_get("finished_at")?;
+        run.cancelled_at = r.try_get("cancelled_at")?;
+        run.failure_reason = r.try_get("failure_reason")?;
+    }
+    run.status = derive_status(&run);
+    if run.status == SyncRunStatus::Completed && run.finished_at.is_none() {
+        run.finished_at = run.bpps.iter().filter_map(|b| b.finished_at).max();
+    }
+    Ok(run)
+}
+
+/// Registers a run unless an overlapping one is still in its sync window,
+/// in which case that run's transaction ID is returned and nothing is
+/// written. Starts are serialized on an advisory lock so two triggers
+/// cannot both pass the check.
+pub async fn insert_sync_run(
+    db_pool: &PgPool,
+    txn_id: &str,
+    trigger: SyncTrigger,
+    bpp_id: Option<&str>,
+) -> Result<Option<String>, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+
+    query("SELECT pg_advisory_xact_lock($1)")
+        .bind(SYNC_START_LOCK)
+        .execute(&mut *tx)
+        .await?;
+
+    if let Some(running) = find_running_sync(&mut *tx, bpp_id).await? {
+        return Ok(Some(running));
+    }
+
+    query(
+        r#"
+        INSERT INTO sync_runs (transaction_id, trigger, bpp_id, expected_bpps)
+        VALUES (
+            $1, $2, $3,
+            CASE
+                WHEN $3::text IS NOT NULL THEN ARRAY[$3::text]
+                ELSE ARRAY(
+                    SELECT DISTINCT bpp_id FROM profiles
+                    WHERE deleted_at IS NULL
+                    ORDER BY bpp_id
+                )
+            END
+        )
+        "#,
+    )
+    .bind(txn_id)
+    .bind(trigger.as_str())
+    .bind(bpp_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(None)
+}
+
+/// Marks a run that never got going, e.g. because the search was rejected.
+pub async fn fail_sync_run(
+    db_pool: &PgPool,
+    txn_id: &str,
+    reason: &str,
+) -> Result<(), sqlx::Error> {
+    query(
+        r#"
+        UPDATE sync_runs
+        SET failure_reason = $2, finished_at = now()
+        WHERE transaction_id = $1
+        "#,
+    )
+    .bind(txn_id)
+    .bind(reason)
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// Adds a page to its BPP's progress. A page delivered twice counts once in
+/// `pages_received` but its items count each time.
+pub async fn record_sync_page(db_pool: &PgPool, page: &SyncPage<'_>) -> Result<(), sqlx::Error> {
+    query(
+        r#"
+        INSERT INTO sync_run_bpps (
+            transaction_id, bpp_id, pages_expected, pages_received,
+            items_received, inserted, updated, unchanged
+        )
+        VALUES ($1, $2, $3, ARRAY[$4::int], $5, $6, $7, $8)
+        ON CONFLICT (transaction_id, bpp_id) DO UPDATE
+        SET
+            pages_expected = COALESCE(EXCLUDED.pages_expected, sync_run_bpps.pages_expected),
+            pages_received = CASE
+                WHEN $4 = ANY(sync_run_bpps.pages_received) THEN sync_run_bpps.pages_received
+                ELSE array_append(sync_run_bpps.pages_received, $4)
+            END,
+            items_received = sync_run_bpps.items_received + EXCLUDED.items_received,
+            inserted = sync_run_bpps.inserted + EXCLUDED.inserted,
+            updated = sync_run_bpps.updated + EXCLUDED.updated,
+            unchanged = sync_run_bpps.unchanged + EXCLUDED.unchanged
+        "#,
+    )
+    .bind(page.transaction_id)
+    .bind(page.bpp_id)
+    .bind(page.pages_expected)
+    .bind(page.page)
+    .bind(page.items)
+    .bind(page.counts.inserted as i32)
+    .bind(page.counts.updated as i32)
+    .bind(page.counts.unchanged as i32)
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn record_sync_error(
+    db_pool: &PgPool,
+    txn_id: &str,
+    bpp_id: &str,
+    message: &str,
+) -> Result<(), sqlx::Error> {
+    let entry = format!("{} {}", Utc::now().to_rfc3339(), message);
+    query(
+        r#"
+        INSERT INTO sync_run_bpps (transaction_id, bpp_id, errors)
+        VALUES ($1, $2, ARRAY[$3])
+        ON CONFLICT (transaction_id, bpp_id) DO UPDATE
+        SET errors = array_append(sync_run_bpps.errors, $3)
+        "#,
+    )
+    .bind(txn_id)
+    .bind(bpp_id)
+    .bind(entry)
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// Records that `bpp_id` delivered every page and its stale rows were
+/// removed, and finishes the run once every expected BPP has finished and
+/// no other BPP that answered is still delivering.
+pub async fn complete_sync_bpp(
+    db_pool: &PgPool,
+    txn_id: &str,
+    bpp_id: &str,
+    stale_deleted: u64,
+) -> Result<(), sqlx::Error> {
+    query(
+        r#"
+        INSERT INTO sync_run_bpps (transaction_id, bpp_id, stale_deleted, finished_at)
+        VALUES ($1, $2, $3, now())
+        ON CONFLICT (transaction_id, bpp_id) DO UPDATE
+        SET stale_deleted = EXCLUDED.stale_deleted, finished_at = EXCLUDED.finished_at
+        "#,
+    )
+    .bind(txn_id)
+    .bind(bpp_id)
+    .bind(stale_deleted as i32)
+    .execute(db_pool)
+    .await?;
+
+    // A run with no expected BPPs cannot tell whether more will answer; its
+    // status follows the sync window instead, see `derive_status`.
+    query(
+        r#"
+        UPDATE sync_runs
+        SET finished_at = now()
+        WHERE transaction_id = $1
+          AND finished_at IS NULL
+          AND cardinality(expected_bpps) > 0
+          AND NOT EXISTS (
+              SELECT 1 FROM sync_run_bpps
+              WHERE transaction_id = $1 AND finished_at IS NULL
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM unnest(expected_bpps) AS expected(bpp_id)
+              WHERE NOT EXISTS (
+                  SELECT 1 FROM sync_run_bpps b
+                  WHERE b.transaction_id = $1
+                    AND b.bpp_id = expected.bpp_id
+                    AND b.finished_at IS NOT NULL
+              )
+          )
+        "#,
+    )
+    .bind(txn_id)
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn is_sync_cancelled(db_pool: &PgPool, txn_id: &str) -> Result<bool, sqlx::Error> {
+    query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM sync_runs
+            WHERE transaction_id = $1 AND cancelled_at IS NOT NULL
+        )
+        "#,
+    )
+    .bind(txn_id)
+    .fetch_one(db_pool)
+    .await
+}
+
+/// Marks the run cancelled so further on_search pages are ignored. Returns
+/// `false` when no run is recorded under `txn_id`.
+pub async fn cancel_sync_run(db_pool: &PgPool, txn_id: &str) -> Result<bool, sqlx::Error> {
+    let result = query(
+        r#"
+        UPDATE sync_runs
+        SET cancelled_at = COALESCE(cancelled_at, now())
+        WHERE transaction_id = $1
+        "#,
+    )
+    .bind(txn_id)
+    .execute(db_pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_sync_run(db_pool: &PgPool, txn_id: &str) -> Result<Option<SyncRun>, sqlx::Error> {
+    let run = query("SELECT * FROM sync_runs WHERE transaction_id = $1")
+        .bind(txn_id)
+        .fetch_optional(db_pool)
+        .await?;
+    let bpps = query("SELECT * FROM sync_run_bpps WHERE transaction_id = $1 ORDER BY bpp_id")
+        .bind(txn_id)
+        .fetch_all(db_pool)
+        .await?
+        .iter()
+        .map(map_bpp)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if run.is_none() && bpps.is_empty() {
+        return Ok(None);
+    }
+    build_run(txn_id.to_string(), run.as_ref(), bpps).map(Some)
+}
+
+/// A run still in its sync window that overlaps a sync of `bpp_id`, or of
+/// every BPP when `bpp_id` is `None`. Covers cron and admin runs alike.
+async fn find_running_sync(
+    executor: impl PgExecutor<'_>,
+    bpp_id: Option<&str>,
+) -> Result<Option<String>, sqlx::Error> {
+    query_scalar(
+        r#"
+        SELECT transaction_id
+        FROM sync_runs
+        WHERE finished_at IS NULL
+          AND cancelled_at IS NULL
+          AND started_at > now() - make_interval(secs => $2)
+          AND ($1::text IS NULL OR bpp_id IS NULL OR bpp_id = $1)
+        ORDER BY started_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(bpp_id)
+    .bind(SYNC_WINDOW_SECONDS as f64)
+    .fetch_optional(executor)
+    .await
+}
+
+/// The most recent runs, newest first.
+pub async fn list_sync_runs(db_pool: &PgPool, limit: i64) -> Result<Vec<SyncRun>, sqlx::Error> {
+    let runs = query("SELECT * FROM sync_runs ORDER BY started_at DESC LIMIT $1")
+        .bind(limit)
+        .fetch_all(db_pool)
+        .await?;
+    let txn_ids = runs
+        .iter()
+        .map(|r| r.try_get("transaction_id"))
+        .collect::<Result<Vec<String>, _>>()?;
+
+    let bpp_rows = query(
+        r#"
+        SELECT * FROM sync_run_bpps
+        WHERE transaction_id = ANY($1)
+        ORDER BY bpp_id
+        "#,
+    )
+    .bind(&txn_ids)
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut result = Vec::with_capacity(runs.len());
+    for (run, txn_id) in runs.iter().zip(txn_ids) {
+        let bpps = bpp_rows
+            .iter()
+            .filter(|r| r.try_get::<&str, _>("transaction_id").ok() == Some(txn_id.as_str()))
+            .map(map_bpp)
+            .collect::<Result<Vec<_>, _>>()?;
+        result.push(build_run(txn_id, Some(run), bpps)?);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn bpp(bpp_id: &str, finished: bool) -> BppSyncProgress {
+        BppSyncProgress {
+            bpp_id: bpp_id.to_string(),
+            pages_expected: Some(1),
+            pages_received: 1,
+            items_received: 0,
+            inserted: 0,
+            updated: 0,
+            unchanged: 0,
+            stale_deleted: None,
+            errors: Vec::new(),
+            started_at: Utc::now(),
+            finished_at: finished.then(Utc::now),
+        }
+    }
+
+    fn run(expected: &[&str], bpps: Vec<BppSyncProgress>, age_seconds: i64) -> SyncRun {
+        SyncRun {
+            transaction_id: "txn".to_string(),
+            trigger: Some(SyncTrigger::Cron),
+            bpp_id: None,
+            expected_bpps: expected.iter().map(|s| s.to_string()).collect(),
+            started_at: Some(Utc::now() - Duration::seconds(age_seconds)),
+            finished_at: None,
+            cancelled_at: None,
+            failure_reason: None,
+            status: SyncRunStatus::Running,
+            bpps,
+        }
+    }
+
+    #[test]
+    fn first_finished_bpp_does_not_complete_the_run() {
+        let r = run(&["a", "b"], vec![bpp("a", true)], 60);
+        assert_eq!(derive_status(&r), SyncRunStatus::Running);
+    }
+
+    #[test]
+    fn run_completes_once_every_expected_bpp_finished() {
+        let r = run(&["a", "b"], vec![bpp("a", true), bpp("b", true)], 60);
+        assert_eq!(derive_status(&r), SyncRunStatus::Completed);
+
+        // A BPP outside the expected set still has to finish.
+        let r = run(&["a"], vec![bpp("a", true), bpp("c", false)], 60);
+        assert_eq!(derive_status(&r), SyncRunStatus::Running);
+    }
+
+    #[test]
+    fn run_without_expected_bpps_waits_for_the_window() {
+        let r = run(&[], vec![bpp("a", true)], 60);
+        assert_eq!(derive_status(&r), SyncRunStatus::Running);
+
+        let r = run(&[], vec![bpp("a", true)], SYNC_WINDOW_SECONDS + 1);
+        assert_eq!(derive_status(&r), SyncRunStatus::Completed);
+    }
+
+    #[test]
+    fn run_missing_a_bpp_after_the_window_is_incomplete() {
+        let r = run(&["a", "b"], vec![bpp("a", true)], SYNC_WINDOW_SECONDS + 1);
+        assert_eq!(derive_status(&r), SyncRunStatus::Incomplete);
+
+        let r = run(&[], vec![bpp("a", false)], SYNC_WINDOW_SECONDS + 1);
+        assert_eq!(derive_status(&r), SyncRunStatus::Incomplete);
+    }
+
+    #[test]
+    fn cancelled_and_failed_runs_keep_their_status() {
+        let mut r = run(&["a"], vec![bpp("a", true)], 60);
+        r.cancelled_at = Some(Utc::now());
+        assert_eq!(derive_status(&r), SyncRunStatus::Cancelled);
+
+        let mut r = run(&["a"], Vec::new(), 60);
+        r.failure_reason = Some("adapter down".to_string());
+        r.finished_at = Some(Utc::now());
+        assert_eq!(derive_status(&r), SyncRunStatus::Failed);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::config::CorsPolicy;
+
+use anyhow::{bail, Context};
+use axum::http::{HeaderName, HeaderValue, Method};
+use std::time::Duration;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer};
+
+/// Builds the CORS layer for a route group, or `None` when the group has no
+/// allowed origins. Combinations the CORS spec forbids (credentials with a
+/// wildcard origin) are rejected here rather than panicking in tower-http.
+pub fn cors_layer(group: &str, policy: &CorsPolicy) -> anyhow::Result<Option<CorsLayer>> {
+    if policy.allowed_origins.is_empty() {
+        return Ok(None);
+    }
+
+    let wildcard = policy.allowed_origins.iter().any(|o| o == "*");
+    if wildcard && policy.allow_credentials {
+        bail!("cors.{group}: allow_credentials needs explicit origins, not \"*\"");
+    }
+
+    let origin = if wildcard {
+        AllowOrigin::from(Any)
+    } else {
+        let origins = policy
+            .allowed_origins
+            .iter()
+            .map(|o| {
+                HeaderValue::from_str(o.trim_end_matches('/'))
+                    .with_context(|| format!("cors.{group}: invalid origin {o}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    let methods = if policy.allowed_methods.is_empty() {
+        AllowMethods::mirror_request()
+    } else {
+        let methods = policy
+            .allowed_methods
+            .iter()
+            .map(|m| {
+                Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                    .with_context(|| format!("cors.{group}: invalid method {m}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        AllowMethods::list(methods)
+    };
+
+    let headers = if policy.allowed_headers.is_empty() {
+        AllowHeaders::mirror_request()
+    } else {
+        let headers = policy
+            .allowed_headers
+            .iter()
+            .map(|h| {
+                HeaderName::from_bytes(h.as_bytes())
+                    .with_context(|| format!("cors.{group}: invalid header {h}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        AllowHeaders::list(headers)
+    };
+
+    let mut layer = CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(policy.allow_credentials);
+
+    if let Some(max_age) = policy.max_age_seconds {
+        layer = layer.max_age(Duration::from_secs(max_age));
+    }
+
+    Ok(Some(layer))
+}
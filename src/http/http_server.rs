@@ -1,22 +1,117 @@
-use crate::cron::start_cron_jobs;
-use crate::state::AppState;
-use crate::{config::AppConfig, http::routes::create_routes};
+use crate::cron::{start_cron_jobs, CronJobs};
+use crate::state::{AppState, SharedConfig};
+use crate::utils::encryption::Keyring;
+use crate::utils::jwt::JwtVerifier;
+use crate::utils::metrics;
+use crate::workers::config_reload::spawn_config_reloader;
+use crate::{
+    config::{AppConfig, CliArgs},
+    http::routes::create_routes,
+    http::tls::{run_https_server, ReloadableTls},
+};
+use axum::Router;
 use deadpool_redis::{Config as RedisConfig, Runtime};
 use sqlx::PgPool;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::{net::TcpListener, sync::watch, task::JoinHandle};
-use tracing::info;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::time::{timeout_at, Duration, Instant};
+use tokio::{
+    net::TcpListener,
+    sync::{watch, Mutex},
+    task::JoinHandle,
+};
+use tokio_util::task::TaskTracker;
+use tracing::{error, info, warn};
+
+type ServerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+/// A running server and the background work it owns.
+pub struct Server {
+    pub http: JoinHandle<ServerResult>,
+    app_state: Arc<AppState>,
+    cron_jobs: Arc<Mutex<CronJobs>>,
+}
+
+impl Server {
+    /// Stops cron triggers, lets open HTTP requests finish, then waits for
+    /// tracked processing tasks and cron runs, all within the configured
+    /// grace period. Whatever is still running at the deadline is dropped.
+    pub async fn shutdown(self, shutdown_tx: watch::Sender<()>) -> ServerResult {
+        let grace = Duration::from_secs(
+            self.app_state
+                .config
+                .current()
+                .shutdown
+                .grace_period_seconds,
+        );
+        let deadline = Instant::now() + grace;
+        info!("🛑 Shutting down (grace period {}s)", grace.as_secs());
+
+        if let Err(e) = self.cron_jobs.lock().await.shutdown().await {
+            error!("Failed to stop cron scheduler: {:?}", e);
+        }
+
+        let _ = shutdown_tx.send(());
+        let served = match timeout_at(deadline, self.http).await {
+            Ok(joined) => joined?,
+            Err(_) => {
+                warn!("⌛ HTTP connections still open at the shutdown deadline");
+                Ok(())
+            }
+        };
+
+        let tasks = &self.app_state.tasks;
+        tasks.close();
+        if !tasks.is_empty() {
+            info!("⏳ Waiting for {} in-flight tasks", tasks.len());
+        }
+        match timeout_at(deadline, tasks.wait()).await {
+            Ok(()) => info!("✅ Shutdown complete"),
+            Err(_) => warn!(
+                "⌛ Shutdown deadline reached with {} tasks still running",
+                tasks.len()
+            ),
+        }
+
+        served
+    }
+}
+
+/// Resolves on the first SIGTERM or SIGINT, returning its name.
+pub async fn shutdown_signal() -> &'static str {
+    let mut terminate = match signal(SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(e) => {
+            error!("Cannot listen for SIGTERM: {:?}", e);
+            let _ = tokio::signal::ctrl_c().await;
+            return "SIGINT";
+        }
+    };
+
+    tokio::select! {
+        _ = terminate.recv() => "SIGTERM",
+        _ = tokio::signal::ctrl_c() => "SIGINT",
+    }
+}
 
 pub async fn start_http_server(
     config: AppConfig,
+    cli: CliArgs,
     shutdown_rx: watch::Receiver<()>,
-) -> Result<
-    JoinHandle<Result<(), Box<dyn std::error::Error + Send + Sync>>>,
-    Box<dyn std::error::Error + Send + Sync>,
-> {
+) -> Result<Server, Box<dyn std::error::Error + Send + Sync>> {
+    let tls = match config.http.tls.clone() {
+        Some(tls_config) => Some(ReloadableTls::load(tls_config)?),
+        None => None,
+    };
+
     let http_addr = format!("{}:{}", config.http.address, config.http.port);
     let listener = tokio::net::TcpListener::bind(http_addr.clone()).await?;
-    info!("🚀 Starting BPP-WEBHOOK server on {:?}", http_addr);
+    info!(
+        "🚀 Starting BPP-WEBHOOK server on {:?} ({})",
+        http_addr,
+        if tls.is_some() { "https" } else { "http" }
+    );
 
     let redis_cfg = RedisConfig::from_url(config.redis.url.as_str());
     let redis_pool = redis_cfg.create_pool(Some(Runtime::Tokio1))?;
@@ -30,31 +125,72 @@ pub async fn start_http_server(
     let db_pool = PgPool::connect(&config.db.url).await?;
     info!("✅ connected to db at {}", &config.db.url);
 
+    let jwt = match config.auth.jwt.clone() {
+        Some(jwt_config) => Some(Arc::new(JwtVerifier::new(jwt_config).await?)),
+        None => None,
+    };
+
+    let keyring = match &config.encryption {
+        Some(encryption) => {
+            let keyring = Keyring::load(&encryption.keyring_path)?;
+            info!(
+                "🔒 Field encryption enabled (active key {})",
+                keyring.active_kid()
+            );
+            Some(Arc::new(keyring))
+        }
+        None => None,
+    };
+
+    let metrics = if config.metrics.enabled {
+        Some(metrics::install()?)
+    } else {
+        None
+    };
+
     let app_state = Arc::new(AppState {
-        config: Arc::new(config.clone()),
+        config: SharedConfig::new(config.clone()),
         redis_pool,
         db_pool,
+        jwt,
+        keyring,
+        tasks: TaskTracker::new(),
+        metrics,
     });
 
-    let _scheduler = start_cron_jobs(app_state.clone()).await;
+    let app = create_routes(app_state.clone())?;
+
+    let cron_jobs = Arc::new(Mutex::new(start_cron_jobs(app_state.clone()).await?));
+    spawn_config_reloader(cli, app_state.config.clone(), cron_jobs.clone());
 
-    let http_server = tokio::spawn(run_http_server(listener, shutdown_rx, app_state.clone()));
+    let http_server = match tls {
+        Some(tls) => {
+            tls.spawn_reloader();
+            tokio::spawn(run_https_server(listener, shutdown_rx, app, tls))
+        }
+        None => tokio::spawn(run_http_server(listener, shutdown_rx, app)),
+    };
 
-    Ok(http_server)
+    Ok(Server {
+        http: http_server,
+        app_state,
+        cron_jobs,
+    })
 }
 
 pub async fn run_http_server(
     listener: TcpListener,
     mut shutdown_rx: watch::Receiver<()>,
-    app_state: Arc<AppState>,
+    app: Router,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let app = create_routes(app_state);
-
-    axum::serve(listener, app.into_make_service())
-        .with_graceful_shutdown(async move {
-            shutdown_rx.changed().await.ok();
-        })
-        .await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        shutdown_rx.changed().await.ok();
+    })
+    .await?;
 
     Ok(())
 }
@@ -1,2 +1,4 @@
+pub mod cors;
 pub mod http_server;
 pub mod routes;
+pub mod tls;
@@ -0,0 +1,49 @@
+use crate::middleware::api_key::require_scope;
+use crate::models::auth::Scope;
+use crate::services::api_key::{
+    handle_issue_api_key, handle_list_api_keys, handle_revoke_api_key, handle_rotate_api_key,
+};
+use crate::services::audit::{handle_list_access_records, handle_profile_access_records};
+use crate::services::consent::{handle_consent_feed, handle_erase_profile, handle_get_consent};
+use crate::services::rate_limit::handle_rate_limit_usage;
+use crate::services::sync::{
+    handle_cancel_sync_run, handle_get_sync_run, handle_list_sync_runs, handle_trigger_sync,
+};
+use crate::state::AppState;
+use axum::{
+    body::Body,
+    http::Request,
+    middleware::{self, Next},
+    routing::{delete, get, post},
+    Router,
+};
+use std::sync::Arc;
+
+pub fn routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/admin/keys",
+            get(handle_list_api_keys).post(handle_issue_api_key),
+        )
+        .route("/admin/keys/{id}", delete(handle_revoke_api_key))
+        .route("/admin/keys/{id}/rotate", post(handle_rotate_api_key))
+        .route("/admin/rate-limits", get(handle_rate_limit_usage))
+        .route(
+            "/admin/sync",
+            get(handle_list_sync_runs).post(handle_trigger_sync),
+        )
+        .route("/admin/sync/{txn_id}", get(handle_get_sync_run))
+        .route("/admin/sync/{txn_id}/cancel", post(handle_cancel_sync_run))
+        .route("/admin/audit/access", get(handle_list_access_records))
+        .route("/admin/consents", post(handle_consent_feed))
+        .route("/admin/consents/{profile_id}", get(handle_get_consent))
+        .route("/admin/profiles/{profile_id}", delete(handle_erase_profile))
+        .route(
+            "/admin/audit/access/profiles/{profile_id}",
+            get(handle_profile_access_records),
+        )
+        .route_layer(middleware::from_fn(|req: Request<Body>, next: Next| {
+            require_scope(Scope::Admin, req, next)
+        }))
+        .with_state(app_state)
+}
@@ -0,0 +1,11 @@
+use crate::services::health::{handle_liveness, handle_readiness};
+use crate::state::AppState;
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/healthz", get(handle_liveness))
+        .route("/readyz", get(handle_readiness))
+        .with_state(app_state)
+}
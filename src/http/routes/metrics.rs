@@ -0,0 +1,29 @@
+use crate::state::AppState;
+use crate::utils::metrics::record_pool_stats;
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+
+async fn metrics_handler(State(app_state): State<Arc<AppState>>) -> Response {
+    let Some(handle) = &app_state.metrics else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    record_pool_stats(&app_state);
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        handle.render(),
+    )
+        .into_response()
+}
+
+pub fn routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(app_state)
+}
@@ -1,11 +1,21 @@
+pub mod admin;
+pub mod health;
+pub mod metrics;
 pub mod profiles;
+pub mod saved_searches;
+pub mod shortlists;
 pub mod webhook;
+use crate::http::cors::cors_layer;
 use crate::middleware::api_key::api_key_auth;
+use crate::middleware::jwt::bearer_auth;
+use crate::middleware::metrics::track_metrics;
+use crate::middleware::rate_limit::api_rate_limit;
+use crate::middleware::trace_context::trace_requests;
 use crate::state::AppState;
 use axum::{middleware, response::IntoResponse, routing::get, Json, Router};
 use chrono::Utc;
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::CorsLayer;
 
 use crate::models::webhook::HealthResponse;
 async fn health_check() -> impl IntoResponse {
@@ -17,22 +27,62 @@ async fn health_check() -> impl IntoResponse {
     Json(response)
 }
 
-pub fn create_routes(app_state: Arc<AppState>) -> Router {
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    let api_routes = Router::new()
-        .merge(profiles::routes(app_state.clone()))
+fn authenticated(router: Router, app_state: &Arc<AppState>) -> Router {
+    router
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            api_rate_limit,
+        ))
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
             api_key_auth,
-        ));
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            bearer_auth,
+        ))
+}
+
+// CORS sits outside authentication so preflight requests, which carry no
+// credentials, are answered before they can be rejected.
+fn with_cors(router: Router, cors: Option<CorsLayer>) -> Router {
+    match cors {
+        Some(cors) => router.layer(cors),
+        None => router,
+    }
+}
+
+pub fn create_routes(app_state: Arc<AppState>) -> anyhow::Result<Router> {
+    let api_cors = cors_layer("api", &app_state.config.current().cors.api)?;
+    let admin_cors = cors_layer("admin", &app_state.config.current().cors.admin)?;
+
+    let admin_routes = with_cors(
+        authenticated(admin::routes(app_state.clone()), &app_state),
+        admin_cors,
+    );
+
+    let api_routes = Router::new()
+        .merge(profiles::routes(app_state.clone()))
+        .merge(saved_searches::routes(app_state.clone()))
+        .merge(shortlists::routes(app_state.clone()));
+    let api_routes = with_cors(authenticated(api_routes, &app_state), api_cors.clone());
+
+    // /webhook is BAP-to-BPP traffic and the probes are for the orchestrator;
+    // neither gets a CORS layer.
+    let mut router = Router::new()
+        .merge(with_cors(
+            Router::new().route("/", get(health_check)),
+            api_cors,
+        ))
+        .nest("/api", api_routes.merge(admin_routes))
+        .merge(webhook::routes(app_state.clone()))
+        .merge(health::routes(app_state.clone()));
+
+    if app_state.metrics.is_some() {
+        router = router.merge(metrics::routes(app_state));
+    }
 
-    Router::new()
-        .route("/", get(health_check))
-        .nest("/api", api_routes)
-        .merge(webhook::routes(app_state))
-        .layer(cors)
+    Ok(router
+        .layer(middleware::from_fn(track_metrics))
+        .layer(middleware::from_fn(trace_requests)))
 }
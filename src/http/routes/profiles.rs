@@ -1,18 +1,62 @@
+use crate::middleware::api_key::require_scope;
+use crate::models::auth::Scope;
+use crate::services::export::{handle_profiles_export, handle_talent_search_export};
+use crate::services::matching::handle_talent_match;
 use crate::services::profile::{
-    handle_candidate_details, handle_market_insights, handle_search, handle_talent_search,
+    handle_candidate_details, handle_list_candidate_unlocks, handle_market_insights, handle_search,
+    handle_talent_search, handle_unlock_candidate,
 };
 use crate::state::AppState;
-use axum::{routing::get, routing::post, Router};
+use axum::{
+    body::Body,
+    http::Request,
+    middleware::{self, Next},
+    routing::get,
+    routing::post,
+    Router,
+};
 use std::sync::Arc;
 
 pub fn routes(app_state: Arc<AppState>) -> Router {
-    Router::new()
+    let search = Router::new()
         .route("/v1/search", post(handle_search))
+        .route("/v1/search/export", post(handle_profiles_export))
         .route("/v1/talent/search", post(handle_talent_search))
+        .route(
+            "/v1/talent/search/export",
+            post(handle_talent_search_export),
+        )
+        .route("/v1/talent/match", post(handle_talent_match))
+        .route_layer(middleware::from_fn(|req: Request<Body>, next: Next| {
+            require_scope(Scope::Search, req, next)
+        }));
+
+    let insights = Router::new()
         .route("/v1/talent/insights", post(handle_market_insights))
+        .route_layer(middleware::from_fn(|req: Request<Body>, next: Next| {
+            require_scope(Scope::Insights, req, next)
+        }));
+
+    let details = Router::new()
         .route(
             "/v1/talent/details/{profile_id}",
             get(handle_candidate_details),
         )
+        .route(
+            "/v1/talent/details/{profile_id}/unlock",
+            post(handle_unlock_candidate),
+        )
+        .route(
+            "/v1/talent/details/{profile_id}/unlocks",
+            get(handle_list_candidate_unlocks),
+        )
+        .route_layer(middleware::from_fn(|req: Request<Body>, next: Next| {
+            require_scope(Scope::Details, req, next)
+        }));
+
+    Router::new()
+        .merge(search)
+        .merge(insights)
+        .merge(details)
         .with_state(app_state)
 }
@@ -0,0 +1,33 @@
+use crate::middleware::api_key::require_scope;
+use crate::models::auth::Scope;
+use crate::services::saved_search::{
+    handle_create_saved_search, handle_delete_saved_search, handle_get_saved_search,
+    handle_list_saved_searches, handle_update_saved_search,
+};
+use crate::state::AppState;
+use axum::{
+    body::Body,
+    http::Request,
+    middleware::{self, Next},
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+
+pub fn routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/v1/saved-searches",
+            get(handle_list_saved_searches).post(handle_create_saved_search),
+        )
+        .route(
+            "/v1/saved-searches/{id}",
+            get(handle_get_saved_search)
+                .put(handle_update_saved_search)
+                .delete(handle_delete_saved_search),
+        )
+        .route_layer(middleware::from_fn(|req: Request<Body>, next: Next| {
+            require_scope(Scope::Search, req, next)
+        }))
+        .with_state(app_state)
+}
@@ -0,0 +1,46 @@
+use crate::middleware::api_key::require_scope;
+use crate::models::auth::Scope;
+use crate::services::shortlist::{
+    handle_add_shortlist_candidate, handle_create_shortlist, handle_delete_shortlist,
+    handle_get_shortlist, handle_list_shortlists, handle_remove_shortlist_candidate,
+    handle_shortlist_candidate_history, handle_update_shortlist, handle_update_shortlist_candidate,
+};
+use crate::state::AppState;
+use axum::{
+    body::Body,
+    http::Request,
+    middleware::{self, Next},
+    routing::{get, patch, post},
+    Router,
+};
+use std::sync::Arc;
+
+pub fn routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/v1/shortlists",
+            get(handle_list_shortlists).post(handle_create_shortlist),
+        )
+        .route(
+            "/v1/shortlists/{id}",
+            get(handle_get_shortlist)
+                .put(handle_update_shortlist)
+                .delete(handle_delete_shortlist),
+        )
+        .route(
+            "/v1/shortlists/{id}/candidates",
+            post(handle_add_shortlist_candidate),
+        )
+        .route(
+            "/v1/shortlists/{id}/candidates/{profile_id}",
+            patch(handle_update_shortlist_candidate).delete(handle_remove_shortlist_candidate),
+        )
+        .route(
+            "/v1/shortlists/{id}/candidates/{profile_id}/history",
+            get(handle_shortlist_candidate_history),
+        )
+        .route_layer(middleware::from_fn(|req: Request<Body>, next: Next| {
+            require_scope(Scope::Details, req, next)
+        }))
+        .with_state(app_state)
+}
@@ -1,10 +1,15 @@
+use crate::middleware::mtls::require_client_cert;
 use crate::services::webhook::{webhook_handler, webhook_handler_profiles};
 use crate::state::AppState;
-use axum::{routing::post, Router};
+use axum::{middleware, routing::post, Router};
 use std::sync::Arc;
 pub fn routes(app_state: Arc<AppState>) -> Router {
     Router::new()
         .route("/webhook/{action}", post(webhook_handler))
         .route("/webhook/profiles/{action}", post(webhook_handler_profiles))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_client_cert,
+        ))
         .with_state(app_state)
 }
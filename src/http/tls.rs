@@ -0,0 +1,225 @@
+use crate::config::TlsConfig;
+
+use anyhow::{bail, Context};
+use axum::{extract::ConnectInfo, Router};
+use hyper::{body::Incoming, Request};
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{conn::auto, graceful::GracefulShutdown},
+    service::TowerToHyperService,
+};
+use rustls::{
+    crypto::{ring, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer},
+    server::{ServerConnection, WebPkiClientVerifier},
+    RootCertStore, ServerConfig,
+};
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::{net::TcpListener, sync::watch};
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+use tracing::{debug, error, info, warn};
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Client certificate presented on the connection, added to every request
+/// served over it. Absent when the client sent none.
+#[derive(Debug, Clone)]
+pub struct ClientCertificate {
+    pub common_name: Option<String>,
+}
+
+impl ClientCertificate {
+    fn from_connection(conn: &ServerConnection) -> Option<Self> {
+        let leaf = conn.peer_certificates()?.first()?;
+        let common_name = x509_parser::parse_x509_certificate(leaf)
+            .ok()
+            .and_then(|(_, cert)| {
+                cert.subject()
+                    .iter_common_name()
+                    .next()
+                    .and_then(|cn| cn.as_str().ok())
+                    .map(str::to_string)
+            });
+        Some(Self { common_name })
+    }
+}
+
+fn load_certs(path: &str) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let mut reader =
+        BufReader::new(File::open(path).with_context(|| format!("opening certificate {path}"))?);
+    let certs = rustls_pemfile::certs(&mut reader)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("reading certificates from {path}"))?;
+    if certs.is_empty() {
+        bail!("no certificates found in {path}");
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &str) -> anyhow::Result<PrivateKeyDer<'static>> {
+    let mut reader =
+        BufReader::new(File::open(path).with_context(|| format!("opening private key {path}"))?);
+    rustls_pemfile::private_key(&mut reader)
+        .with_context(|| format!("reading private key from {path}"))?
+        .with_context(|| format!("no private key found in {path}"))
+}
+
+fn build_server_config(tls: &TlsConfig) -> anyhow::Result<ServerConfig> {
+    let provider: Arc<CryptoProvider> = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    // Client certificates are optional at the handshake so browsers and API
+    // callers can still connect; /webhook insists on one in middleware.
+    let builder = match &tls.client_ca_path {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(ca_path)? {
+                roots.add(cert)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .allow_unauthenticated()
+                .build()?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config =
+        builder.with_single_cert(load_certs(&tls.cert_path)?, load_key(&tls.key_path)?)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// Server TLS settings that can be swapped while the listener keeps
+/// running. New connections pick up the latest certificates; existing
+/// ones keep the config they were accepted with.
+pub struct ReloadableTls {
+    tls: TlsConfig,
+    current: RwLock<Arc<ServerConfig>>,
+}
+
+impl ReloadableTls {
+    pub fn load(tls: TlsConfig) -> anyhow::Result<Arc<Self>> {
+        let config = build_server_config(&tls)?;
+        Ok(Arc::new(Self {
+            tls,
+            current: RwLock::new(Arc::new(config)),
+        }))
+    }
+
+    fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.current.read().unwrap().clone())
+    }
+
+    fn modified(&self) -> Vec<Option<SystemTime>> {
+        [
+            Some(&self.tls.cert_path),
+            Some(&self.tls.key_path),
+            self.tls.client_ca_path.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+    }
+
+    /// Polls the certificate, key and client CA files and reloads them
+    /// when any changes. A file that fails to load keeps the previous
+    /// config in place and is retried on the next tick.
+    pub fn spawn_reloader(self: &Arc<Self>) {
+        if self.tls.reload_interval_seconds == 0 {
+            return;
+        }
+
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut seen = this.modified();
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(this.tls.reload_interval_seconds));
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                let modified = this.modified();
+                if modified == seen {
+                    continue;
+                }
+
+                match build_server_config(&this.tls) {
+                    Ok(config) => {
+                        *this.current.write().unwrap() = Arc::new(config);
+                        seen = modified;
+                        info!("🔐 Reloaded TLS certificates from {}", this.tls.cert_path);
+                    }
+                    Err(e) => error!("TLS reload failed, keeping previous certificates: {:?}", e),
+                }
+            }
+        });
+    }
+}
+
+pub async fn run_https_server(
+    listener: TcpListener,
+    mut shutdown_rx: watch::Receiver<()>,
+    app: Router,
+    tls: Arc<ReloadableTls>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let graceful = GracefulShutdown::new();
+
+    loop {
+        let (stream, addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("Failed to accept connection: {:?}", e);
+                    continue;
+                }
+            },
+            _ = shutdown_rx.changed() => break,
+        };
+
+        let acceptor = tls.acceptor();
+        let app = app.clone();
+        let watcher = graceful.watcher();
+
+        tokio::spawn(async move {
+            let stream =
+                match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        debug!("TLS handshake with {} failed: {:?}", addr, e);
+                        return;
+                    }
+                    Err(_) => {
+                        debug!("TLS handshake with {} timed out", addr);
+                        return;
+                    }
+                };
+
+            let client_cert = ClientCertificate::from_connection(stream.get_ref().1);
+            let service = app.map_request(move |mut req: Request<Incoming>| {
+                req.extensions_mut().insert(ConnectInfo::<SocketAddr>(addr));
+                if let Some(cert) = &client_cert {
+                    req.extensions_mut().insert(cert.clone());
+                }
+                req
+            });
+
+            let builder = auto::Builder::new(TokioExecutor::new());
+            let conn =
+                builder.serve_connection(TokioIo::new(stream), TowerToHyperService::new(service));
+            if let Err(e) = watcher.watch(conn).await {
+                debug!("Connection from {} closed with error: {:?}", addr, e);
+            }
+        });
+    }
+
+    graceful.shutdown().await;
+    Ok(())
+}
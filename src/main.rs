@@ -1,21 +1,64 @@
 use tokio::sync::watch;
+use tracing::{info, warn};
 
 use bpp_onest_lite::{
-    config::AppConfig, http::http_server::start_http_server, utils::logging::setup_logging,
+    config::{AppConfig, CliArgs},
+    http::http_server::{shutdown_signal, start_http_server},
+    utils::logging::setup_logging,
+    workers::key_rotation,
 };
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let _guard = setup_logging("app/logs", "bap-adapter");
-    let config = AppConfig::new()?;
+    let cli = CliArgs::from_env()?;
+    let config = AppConfig::load(&cli)?;
+    let _guard = if cli.command.as_deref() == Some("check-config") {
+        // Checking a config must not create log files.
+        let mut check_config = config.clone();
+        check_config.logging.file.enabled = false;
+        setup_logging(&check_config)?
+    } else {
+        setup_logging(&config)?
+    };
 
-    let (_shutdown_tx, shutdown_rx) = watch::channel(());
+    match cli.command.as_deref() {
+        None | Some("serve") => {}
+        Some("check-config") => {
+            println!("{}", serde_json::to_string_pretty(&config.masked())?);
+            println!("✅ Configuration is valid");
+            return Ok(());
+        }
+        Some("rotate-keys") => {
+            let summary = key_rotation::run(&config).await?;
+            if summary.failed > 0 {
+                return Err(
+                    format!("{} profiles could not be re-encrypted", summary.failed).into(),
+                );
+            }
+            if summary.skipped > 0 {
+                warn!(
+                    "{} profiles changed during rotation; run rotate-keys again to cover them",
+                    summary.skipped
+                );
+            }
+            return Ok(());
+        }
+        Some(other) => {
+            return Err(format!(
+                "Unknown command: {other} (expected serve, check-config or rotate-keys)"
+            )
+            .into())
+        }
+    }
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(());
 
-    let http_server = start_http_server(config, shutdown_rx).await?;
+    let mut server = start_http_server(config, cli, shutdown_rx).await?;
 
     tokio::select! {
-        res = http_server => res??,
+        res = &mut server.http => return res?,
+        signal = shutdown_signal() => info!("Received {}", signal),
     }
 
-    Ok(())
+    server.shutdown(shutdown_tx).await
 }
@@ -8,27 +8,136 @@ use axum::{
 };
 use serde_json::json;
 use std::sync::Arc;
+use tracing::error;
 
+use crate::config::AuthConfig;
+use crate::db::api_key::find_active_api_key_by_prefix;
+use crate::models::auth::{AuthMethod, Caller, CallerRole, Scope};
 use crate::state::AppState;
+use crate::utils::api_keys::{key_prefix, secrets_match, verify_api_key};
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({
+            "error": "Unauthorized",
+            "message": "Invalid or missing X-API-KEY"
+        })),
+    )
+        .into_response()
+}
+
+fn config_caller(auth: &AuthConfig, key: &str) -> Option<Caller> {
+    let privileged = auth
+        .privileged_keys
+        .iter()
+        .find(|k| secrets_match(key, &k.key))
+        .map(|k| Caller {
+            id: format!("config:{}", k.name),
+            auth_method: AuthMethod::ApiKey,
+            role: CallerRole::Privileged,
+            scopes: Scope::ALL.to_vec(),
+            rate_limit_per_minute: None,
+        });
+
+    privileged.or_else(|| {
+        auth.x_api_key
+            .as_deref()
+            .filter(|expected| secrets_match(key, expected))
+            .map(|_| Caller {
+                id: "config:default".to_string(),
+                auth_method: AuthMethod::ApiKey,
+                role: CallerRole::Standard,
+                scopes: auth.x_api_key_scopes.clone(),
+                rate_limit_per_minute: None,
+            })
+    })
+}
+
+async fn stored_caller(state: &AppState, key: &str) -> Result<Option<Caller>, sqlx::Error> {
+    let Some(prefix) = key_prefix(key) else {
+        return Ok(None);
+    };
+
+    let Some(record) = find_active_api_key_by_prefix(&state.db_pool, prefix).await? else {
+        return Ok(None);
+    };
+
+    if !verify_api_key(key, &record.key_hash) {
+        return Ok(None);
+    }
+
+    Ok(Some(Caller {
+        id: format!("key:{}", record.lineage_id),
+        auth_method: AuthMethod::ApiKey,
+        role: CallerRole::parse(&record.role).unwrap_or_default(),
+        scopes: record
+            .scopes
+            .iter()
+            .filter_map(|s| Scope::parse(s))
+            .collect(),
+        rate_limit_per_minute: record.rate_limit_per_minute.map(|l| l.max(1) as u32),
+    }))
+}
 
 pub async fn api_key_auth(
     State(state): State<Arc<AppState>>,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Response {
-    let expected_key = &state.config.auth.x_api_key;
+    // Already authenticated by the bearer-token layer.
+    if req.extensions().get::<Caller>().is_some() {
+        return next.run(req).await;
+    }
+
+    let Some(provided_key) = req
+        .headers()
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+    else {
+        return unauthorized();
+    };
+
+    let caller = match stored_caller(&state, &provided_key).await {
+        Ok(Some(caller)) => caller,
+        Ok(None) => match config_caller(&state.config.current().auth, &provided_key) {
+            Some(caller) => caller,
+            None => return unauthorized(),
+        },
+        Err(e) => {
+            error!("API key lookup failed: {:?}", e);
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({
+                    "error": "Service Unavailable",
+                    "message": "Unable to verify X-API-KEY"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    req.extensions_mut().insert(caller);
+    next.run(req).await
+}
 
-    let provided_key = req.headers().get("x-api-key").and_then(|v| v.to_str().ok());
+pub async fn require_scope(scope: Scope, req: Request<Body>, next: Next) -> Response {
+    let allowed = req
+        .extensions()
+        .get::<Caller>()
+        .is_some_and(|c| c.has_scope(scope));
 
-    match provided_key {
-        Some(key) if key == expected_key => next.run(req).await,
-        _ => (
-            StatusCode::UNAUTHORIZED,
+    if allowed {
+        next.run(req).await
+    } else {
+        (
+            StatusCode::FORBIDDEN,
             Json(json!({
-                "error": "Unauthorized",
-                "message": "Invalid or missing X-API-KEY"
+                "error": "Forbidden",
+                "message": format!("This API key lacks the '{}' scope", scope.as_str())
             })),
         )
-            .into_response(),
+            .into_response()
     }
 }
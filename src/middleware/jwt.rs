@@ -0,0 +1,61 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::state::AppState;
+
+fn unauthorized(message: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        Json(json!({
+            "error": "Unauthorized",
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+/// Authenticates `Authorization: Bearer <jwt>` requests. Requests without a
+/// bearer token fall through to `api_key_auth`.
+pub async fn bearer_auth(
+    State(state): State<Arc<AppState>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(token) = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| {
+            v.split_once(' ')
+                .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        })
+        .map(|(_, token)| token.trim().to_string())
+    else {
+        return next.run(req).await;
+    };
+
+    let Some(verifier) = state.jwt.as_ref() else {
+        return unauthorized("Bearer authentication is not enabled");
+    };
+
+    match verifier.verify(&token).await {
+        Ok(caller) => {
+            req.extensions_mut().insert(caller);
+            next.run(req).await
+        }
+        Err(e) => {
+            warn!("Bearer token rejected: {}", e);
+            unauthorized("Invalid bearer token")
+        }
+    }
+}
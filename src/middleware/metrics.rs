@@ -0,0 +1,33 @@
+use axum::{body::Body, extract::MatchedPath, http::Request, middleware::Next, response::Response};
+use std::time::Instant;
+
+use crate::utils::metrics::{action_label, record_request};
+
+/// Counts and times every request by route template, and by Beckn action
+/// for the `{action}` webhook routes.
+pub async fn track_metrics(req: Request<Body>, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+
+    let action = match &route {
+        Some(route) if route.ends_with("{action}") => {
+            action_label(req.uri().path().rsplit('/').next().unwrap_or_default())
+        }
+        _ => "",
+    };
+
+    let response = next.run(req).await;
+
+    record_request(
+        &method,
+        route.as_deref().unwrap_or("unmatched"),
+        action,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
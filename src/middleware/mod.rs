@@ -1 +1,6 @@
 pub mod api_key;
+pub mod jwt;
+pub mod metrics;
+pub mod mtls;
+pub mod rate_limit;
+pub mod trace_context;
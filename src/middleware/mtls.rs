@@ -0,0 +1,69 @@
+use crate::http::tls::ClientCertificate;
+use crate::models::webhook::AckResponse;
+use crate::state::AppState;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Requires a verified client certificate with an allow-listed CN on the
+/// webhook routes whenever mutual TLS is configured. The handshake already
+/// checked the chain against `client_ca_path`.
+pub async fn require_client_cert(
+    State(app_state): State<Arc<AppState>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let config = app_state.config.current();
+    let Some(tls) = config
+        .http
+        .tls
+        .as_ref()
+        .filter(|tls| tls.client_ca_path.is_some())
+    else {
+        return next.run(req).await;
+    };
+
+    let Some(cert) = req.extensions().get::<ClientCertificate>() else {
+        warn!(target: "webhook", "⛔ Webhook call without a client certificate");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(AckResponse::nack(
+                "CLIENT_CERT_REQUIRED",
+                "A client certificate is required".to_string(),
+            )),
+        )
+            .into_response();
+    };
+
+    let allowed = tls.webhook_allowed_cns.is_empty()
+        || cert
+            .common_name
+            .as_ref()
+            .is_some_and(|cn| tls.webhook_allowed_cns.contains(cn));
+
+    if !allowed {
+        warn!(
+            target: "webhook",
+            "⛔ Webhook call from client certificate CN {:?} not in allow-list",
+            cert.common_name
+        );
+        return (
+            StatusCode::FORBIDDEN,
+            Json(AckResponse::nack(
+                "CLIENT_CERT_REJECTED",
+                "Client certificate is not allowed to call this endpoint".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    next.run(req).await
+}
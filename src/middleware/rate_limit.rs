@@ -0,0 +1,54 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::models::auth::Caller;
+use crate::state::AppState;
+use crate::utils::rate_limit::enforce;
+
+/// Per-caller token bucket for /api. Runs after authentication so the
+/// bucket follows the caller identity rather than the client address.
+pub async fn api_rate_limit(
+    State(state): State<Arc<AppState>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(caller) = req.extensions().get::<Caller>() else {
+        return next.run(req).await;
+    };
+
+    let limit = state
+        .config
+        .current()
+        .rate_limits
+        .for_caller(&caller.id, caller.rate_limit_per_minute);
+
+    let Some(limit) = limit else {
+        return next.run(req).await;
+    };
+
+    let bucket = format!("api:{}:{}", caller.auth_method.as_str(), caller.id);
+    if let Some(decision) = enforce(&state.redis_pool, &bucket, &limit).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, decision.retry_after_secs.to_string())],
+            Json(json!({
+                "error": "Too Many Requests",
+                "message": format!(
+                    "Rate limit of {} requests per {}s exceeded",
+                    limit.requests, limit.period_seconds
+                )
+            })),
+        )
+            .into_response();
+    }
+
+    next.run(req).await
+}
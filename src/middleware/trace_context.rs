@@ -0,0 +1,29 @@
+use axum::{body::Body, extract::MatchedPath, http::Request, middleware::Next, response::Response};
+use tracing::{field::Empty, info_span, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::utils::telemetry::extract_trace_context;
+
+/// Opens a server span per request, continuing the caller's trace when it
+/// sends a W3C `traceparent` header.
+pub async fn trace_requests(req: Request<Body>, next: Next) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let span = info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", req.method(), route),
+        otel.kind = "server",
+        http.request.method = %req.method(),
+        http.route = %route,
+        http.response.status_code = Empty,
+    );
+    let _ = span.set_parent(extract_trace_context(req.headers()));
+
+    let response = next.run(req).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
+    response
+}
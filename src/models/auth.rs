@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CallerRole {
+    #[default]
+    Standard,
+    Privileged,
+}
+
+impl CallerRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallerRole::Standard => "standard",
+            CallerRole::Privileged => "privileged",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "standard" => Some(CallerRole::Standard),
+            "privileged" => Some(CallerRole::Privileged),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    Search,
+    Insights,
+    Details,
+    Admin,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 4] = [Scope::Search, Scope::Insights, Scope::Details, Scope::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Search => "search",
+            Scope::Insights => "insights",
+            Scope::Details => "details",
+            Scope::Admin => "admin",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Scope::ALL.iter().copied().find(|scope| scope.as_str() == s)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    ApiKey,
+    Bearer,
+}
+
+impl AuthMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthMethod::ApiKey => "api_key",
+            AuthMethod::Bearer => "bearer",
+        }
+    }
+}
+
+/// Identity of the authenticated API caller, inserted into request
+/// extensions by the auth middleware.
+#[derive(Debug, Clone)]
+pub struct Caller {
+    /// Namespaced by source: `key:<lineage_id>` for issued keys,
+    /// `config:<name>` for keys from config and `jwt:<sub>` for bearer tokens.
+    pub id: String,
+    pub auth_method: AuthMethod,
+    pub role: CallerRole,
+    pub scopes: Vec<Scope>,
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+impl Caller {
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub role: Option<CallerRole>,
+    pub rate_limit_per_minute: Option<u32>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct RotateApiKeyRequest {
+    /// Seconds the old key keeps working after rotation; 0 revokes it now.
+    pub grace_seconds: Option<u64>,
+}
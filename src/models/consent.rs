@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsentEntry {
+    pub profile_id: String,
+    #[serde(default)]
+    pub purposes: Vec<String>,
+    pub granted_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub withdrawn: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsentFeedRequest {
+    pub consents: Vec<ConsentEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ErasureRequest {
+    pub reason: Option<String>,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ExportQuery {
+    pub format: Option<ExportFormat>,
+    /// Comma-separated column names, e.g. `profile_id,name,role`.
+    pub columns: Option<String>,
+}
@@ -0,0 +1,39 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    Ok,
+    Down,
+    Stale,
+    Unknown,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentHealth {
+    pub status: ComponentState,
+    /// Whether this component being unhealthy makes the service not ready.
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_completed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_seconds: Option<i64>,
+}
+
+impl ComponentHealth {
+    pub fn is_blocking(&self) -> bool {
+        self.required && self.status != ComponentState::Ok
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: &'static str,
+    pub timestamp: String,
+    pub components: BTreeMap<&'static str, ComponentHealth>,
+}
@@ -0,0 +1,59 @@
+use crate::db::profile::SampleCandidate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TalentMatchRequest {
+    pub role: Option<String>,
+    pub location: Option<String>,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    pub radius_km: Option<f64>,
+    pub qualification: Option<String>,
+    pub experience_min: Option<f64>,
+    pub experience_max: Option<f64>,
+    pub job_type: Option<String>,
+    pub pay_min: Option<f64>,
+    pub pay_max: Option<f64>,
+    pub min_score: Option<f64>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchCriterion {
+    Role,
+    Location,
+    Experience,
+    Qualification,
+    JobType,
+    Pay,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CriterionScore {
+    pub criterion: MatchCriterion,
+    pub weight: f64,
+    pub score: f64,
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatchedCandidate {
+    pub profile_id: String,
+    pub score: f64,
+    pub candidate: SampleCandidate,
+    pub breakdown: Vec<CriterionScore>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TalentMatchResponse {
+    pub pool_size: usize,
+    pub matched_count: usize,
+    /// More profiles passed the pre-filters than are scored; only the most
+    /// recently updated ones were ranked.
+    pub truncated: bool,
+    pub results: Vec<MatchedCandidate>,
+    pub page: u32,
+    pub limit: u32,
+}
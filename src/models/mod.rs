@@ -1,4 +1,13 @@
+pub mod audit;
+pub mod auth;
+pub mod consent;
 pub mod core;
+pub mod export;
+pub mod health;
+pub mod matching;
 pub mod profiles;
+pub mod saved_search;
 pub mod search;
+pub mod shortlist;
+pub mod sync;
 pub mod webhook;
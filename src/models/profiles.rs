@@ -6,3 +6,8 @@ pub struct ProfileSearchRequest {
     pub query: Option<String>,
     pub pagination: Option<Pagination>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnlockRequest {
+    pub reason: String,
+}
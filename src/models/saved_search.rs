@@ -0,0 +1,11 @@
+use crate::models::search::TalentSearchRequest;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedSearchRequest {
+    pub name: String,
+    pub query: TalentSearchRequest,
+    pub schedule_seconds: Option<i64>,
+    pub webhook_url: Option<String>,
+    pub active: Option<bool>,
+}
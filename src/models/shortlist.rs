@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PipelineStage {
+    #[default]
+    Shortlisted,
+    Contacted,
+    Interviewed,
+    Rejected,
+    Hired,
+}
+
+impl PipelineStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipelineStage::Shortlisted => "shortlisted",
+            PipelineStage::Contacted => "contacted",
+            PipelineStage::Interviewed => "interviewed",
+            PipelineStage::Rejected => "rejected",
+            PipelineStage::Hired => "hired",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShortlistRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddCandidateRequest {
+    pub profile_id: String,
+    pub stage: Option<PipelineStage>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCandidateRequest {
+    pub stage: Option<PipelineStage>,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncTrigger {
+    Cron,
+    Admin,
+}
+
+impl SyncTrigger {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncTrigger::Cron => "cron",
+            SyncTrigger::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "cron" => Some(SyncTrigger::Cron),
+            "admin" => Some(SyncTrigger::Admin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncRunStatus {
+    /// Pages are still arriving, or no BPP has answered yet.
+    Running,
+    /// Every expected BPP, and every other BPP that answered, delivered all
+    /// its pages.
+    Completed,
+    /// The pagination window passed before every BPP finished.
+    Incomplete,
+    Cancelled,
+    /// The search never reached the BAP adapter.
+    Failed,
+}
+
+/// Progress of one BPP within a run.
+#[derive(Debug, Serialize)]
+pub struct BppSyncProgress {
+    pub bpp_id: String,
+    /// `None` until a page reports a total.
+    pub pages_expected: Option<i32>,
+    pub pages_received: i32,
+    pub items_received: i32,
+    pub inserted: i32,
+    pub updated: i32,
+    pub unchanged: i32,
+    pub stale_deleted: Option<i32>,
+    pub errors: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncRun {
+    pub transaction_id: String,
+    pub trigger: Option<SyncTrigger>,
+    /// Set when the run targeted a single BPP.
+    pub bpp_id: Option<String>,
+    /// BPPs the run waits for: the targeted BPP, or every BPP with active
+    /// profiles when a full run started.
+    pub expected_bpps: Vec<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the last BPP finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub failure_reason: Option<String>,
+    pub status: SyncRunStatus,
+    pub bpps: Vec<BppSyncProgress>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct TriggerSyncRequest {
+    /// Sync one BPP instead of searching the whole network.
+    pub bpp_id: Option<String>,
+    /// Defaults to the URI stored with the BPP's profiles.
+    pub bpp_uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct SyncRunsQuery {
+    pub limit: Option<i64>,
+}
@@ -31,6 +31,31 @@ pub struct WebhookPayload {
 #[derive(Debug, Serialize)]
 pub struct AckResponse {
     pub message: AckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<AckError>,
+}
+
+impl AckResponse {
+    pub fn ack() -> Self {
+        Self {
+            message: AckStatus {
+                ack: Ack { status: "ACK" },
+            },
+            error: None,
+        }
+    }
+
+    pub fn nack(code: &str, message: String) -> Self {
+        Self {
+            message: AckStatus {
+                ack: Ack { status: "NACK" },
+            },
+            error: Some(AckError {
+                code: code.to_string(),
+                message,
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -42,3 +67,9 @@ pub struct AckStatus {
 pub struct Ack {
     pub status: &'static str,
 }
+
+#[derive(Debug, Serialize)]
+pub struct AckError {
+    pub code: String,
+    pub message: String,
+}
@@ -0,0 +1,210 @@
+use crate::db::api_key::{
+    expire_api_key, get_api_key, insert_api_key, list_api_keys, revoke_api_key, ApiKeyRecord,
+    NewApiKey,
+};
+use crate::models::auth::{Caller, CallerRole, IssueApiKeyRequest, RotateApiKeyRequest};
+use crate::state::AppState;
+use crate::utils::api_keys::{generate_api_key, hash_api_key};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::Utc;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+type ApiError = (StatusCode, Json<Value>);
+
+fn error_response(status: StatusCode, message: &str) -> ApiError {
+    (
+        status,
+        Json(serde_json::json!({
+            "error": message
+        })),
+    )
+}
+
+fn db_error(context: &str, err: sqlx::Error) -> ApiError {
+    match err.as_database_error().and_then(|e| e.code()).as_deref() {
+        Some("23505") => error_response(
+            StatusCode::CONFLICT,
+            "An API key with this name already exists",
+        ),
+        _ => {
+            tracing::error!("{} failed: {:?}", context, err);
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to process API key",
+            )
+        }
+    }
+}
+
+/// Only privileged callers may manage privileged keys, otherwise a standard
+/// admin could escalate to unredacted candidate details or lock out the
+/// privileged operators.
+fn require_privileged_for(caller: &Caller, role: CallerRole) -> Result<(), ApiError> {
+    if role == CallerRole::Privileged && caller.role != CallerRole::Privileged {
+        return Err(error_response(
+            StatusCode::FORBIDDEN,
+            "Only privileged callers can issue, rotate or revoke privileged keys",
+        ));
+    }
+    Ok(())
+}
+
+async fn find_unrevoked_key(app_state: &AppState, id: Uuid) -> Result<ApiKeyRecord, ApiError> {
+    match get_api_key(&app_state.db_pool, id).await {
+        Ok(Some(k)) if k.revoked_at.is_none() => Ok(k),
+        Ok(_) => Err(error_response(StatusCode::NOT_FOUND, "API key not found")),
+        Err(e) => Err(db_error("get_api_key", e)),
+    }
+}
+
+fn issued(key: String, record: ApiKeyRecord) -> Json<Value> {
+    Json(serde_json::json!({
+        "key": key,
+        "api_key": record,
+        "note": "Store this key now; it cannot be retrieved again."
+    }))
+}
+
+pub async fn handle_issue_api_key(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Json(req): Json<IssueApiKeyRequest>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    if req.name.trim().is_empty() || req.scopes.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "name and at least one scope are required",
+        ));
+    }
+    let rate_limit_per_minute = match req.rate_limit_per_minute {
+        Some(limit) => Some(
+            i32::try_from(limit)
+                .ok()
+                .filter(|l| *l > 0)
+                .ok_or_else(|| {
+                    error_response(
+                        StatusCode::BAD_REQUEST,
+                        "rate_limit_per_minute must be between 1 and 2147483647",
+                    )
+                })?,
+        ),
+        None => None,
+    };
+    let role = req.role.unwrap_or_default();
+    require_privileged_for(&caller, role)?;
+
+    let (key, prefix) = generate_api_key();
+    let key_hash = hash_api_key(&key);
+    let mut scopes: Vec<String> = req.scopes.iter().map(|s| s.as_str().to_string()).collect();
+    scopes.sort();
+    scopes.dedup();
+
+    let record = insert_api_key(
+        &app_state.db_pool,
+        &NewApiKey {
+            name: req.name.trim(),
+            key_prefix: &prefix,
+            key_hash: &key_hash,
+            scopes,
+            role: role.as_str(),
+            rate_limit_per_minute,
+            expires_at: req.expires_at,
+            rotated_from: None,
+        },
+    )
+    .await
+    .map_err(|e| db_error("insert_api_key", e))?;
+
+    info!(
+        "🔑 API key issued: name={}, prefix={}, scopes={:?}, by={}",
+        record.name, record.key_prefix, record.scopes, caller.id
+    );
+
+    Ok((StatusCode::CREATED, issued(key, record)))
+}
+
+pub async fn handle_rotate_api_key(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+    body: Option<Json<RotateApiKeyRequest>>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let req = body.map(|Json(r)| r).unwrap_or_default();
+
+    let old = find_unrevoked_key(&app_state, id).await?;
+    require_privileged_for(&caller, CallerRole::parse(&old.role).unwrap_or_default())?;
+    // The replacement inherits the expiry, so an expired key would be
+    // rotated into another dead key.
+    if old.expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(error_response(
+            StatusCode::CONFLICT,
+            "Expired API keys cannot be rotated; issue a new key instead",
+        ));
+    }
+
+    let (key, prefix) = generate_api_key();
+    let key_hash = hash_api_key(&key);
+
+    let record = insert_api_key(
+        &app_state.db_pool,
+        &NewApiKey {
+            name: &old.name,
+            key_prefix: &prefix,
+            key_hash: &key_hash,
+            scopes: old.scopes.clone(),
+            role: &old.role,
+            rate_limit_per_minute: old.rate_limit_per_minute,
+            expires_at: old.expires_at,
+            rotated_from: Some(old.id),
+        },
+    )
+    .await
+    .map_err(|e| db_error("insert_api_key", e))?;
+
+    let grace_seconds = req.grace_seconds.unwrap_or(0);
+    expire_api_key(&app_state.db_pool, old.id, grace_seconds)
+        .await
+        .map_err(|e| db_error("expire_api_key", e))?;
+
+    info!(
+        "🔁 API key rotated: name={}, old_prefix={}, new_prefix={}, grace={}s, by={}",
+        record.name, old.key_prefix, record.key_prefix, grace_seconds, caller.id
+    );
+
+    Ok((StatusCode::CREATED, issued(key, record)))
+}
+
+pub async fn handle_list_api_keys(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Vec<ApiKeyRecord>>, ApiError> {
+    list_api_keys(&app_state.db_pool)
+        .await
+        .map(Json)
+        .map_err(|e| db_error("list_api_keys", e))
+}
+
+pub async fn handle_revoke_api_key(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let key = find_unrevoked_key(&app_state, id).await?;
+    require_privileged_for(&caller, CallerRole::parse(&key.role).unwrap_or_default())?;
+
+    match revoke_api_key(&app_state.db_pool, id).await {
+        Ok(true) => {
+            info!("⛔ API key revoked: id={}, by={}", id, caller.id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => Err(error_response(StatusCode::NOT_FOUND, "API key not found")),
+        Err(e) => Err(db_error("revoke_api_key", e)),
+    }
+}
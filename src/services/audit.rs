@@ -0,0 +1,67 @@
+use crate::db::access_audit::{list_access_records, AccessRecordFilter};
+use crate::models::audit::AccessAuditQuery;
+use crate::state::AppState;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::error;
+
+async fn list(
+    app_state: &AppState,
+    filter: AccessRecordFilter,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    match list_access_records(&app_state.db_pool, &filter).await {
+        Ok(result) => Ok(Json(serde_json::json!({
+            "items": result.items,
+            "total": result.total,
+            "page": result.page,
+            "limit": result.limit
+        }))),
+        Err(e) => {
+            error!("list_access_records failed: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": "Failed to read access log"
+                })),
+            ))
+        }
+    }
+}
+
+fn filter_from(query: AccessAuditQuery) -> AccessRecordFilter {
+    AccessRecordFilter {
+        profile_id: query.profile_id,
+        caller_id: query.caller_id,
+        action: query.action,
+        from: query.from,
+        to: query.to,
+        page: query.page.unwrap_or(1),
+        limit: query.limit.unwrap_or(50),
+    }
+}
+
+pub async fn handle_list_access_records(
+    State(app_state): State<Arc<AppState>>,
+    Query(query): Query<AccessAuditQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    list(&app_state, filter_from(query)).await
+}
+
+/// "Who accessed profile X": the access log narrowed to one profile.
+pub async fn handle_profile_access_records(
+    State(app_state): State<Arc<AppState>>,
+    Path(profile_id): Path<String>,
+    Query(query): Query<AccessAuditQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let filter = AccessRecordFilter {
+        profile_id: Some(profile_id),
+        ..filter_from(query)
+    };
+    list(&app_state, filter).await
+}
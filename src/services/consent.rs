@@ -0,0 +1,181 @@
+use crate::db::consent::{erase_profile, get_consent, upsert_consents, NewConsent};
+use crate::db::profile::NewProfile;
+use crate::models::auth::Caller;
+use crate::models::consent::{ConsentFeedRequest, ErasureRequest};
+use crate::state::AppState;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{error, info};
+
+type ApiError = (StatusCode, Json<Value>);
+
+fn error_response(status: StatusCode, message: &str) -> ApiError {
+    (
+        status,
+        Json(serde_json::json!({
+            "error": message
+        })),
+    )
+}
+
+fn field<'a>(value: &'a Value, names: &[&str]) -> Option<&'a Value> {
+    names.iter().find_map(|n| value.get(*n))
+}
+
+fn timestamp(value: &Value, names: &[&str]) -> Option<DateTime<Utc>> {
+    field(value, names)
+        .and_then(Value::as_str)
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Reads the consent object at `path` (dot-separated) of a beckn item.
+/// Accepts `purposes` as an array or a single `purpose` string, and a
+/// `status` of withdrawn/revoked to mark consent as pulled.
+fn parse_payload_consent(profile_id: &str, item: &Value, path: &str) -> Option<NewConsent> {
+    let consent = path
+        .split('.')
+        .filter(|s| !s.is_empty())
+        .try_fold(item, |v, key| v.get(key))?;
+
+    let purposes = match field(consent, &["purposes", "purpose"])? {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Value::String(s) => vec![s.clone()],
+        _ => return None,
+    };
+
+    let withdrawn = field(consent, &["status"])
+        .and_then(Value::as_str)
+        .is_some_and(|s| s.eq_ignore_ascii_case("withdrawn") || s.eq_ignore_ascii_case("revoked"));
+
+    Some(NewConsent {
+        profile_id: profile_id.to_string(),
+        purposes,
+        granted_at: timestamp(consent, &["granted_at", "grantedAt"]),
+        expires_at: timestamp(
+            consent,
+            &["expires_at", "expiresAt", "valid_until", "validUntil"],
+        ),
+        withdrawn,
+    })
+}
+
+pub fn consents_from_profiles(profiles: &[NewProfile], path: &str) -> Vec<NewConsent> {
+    profiles
+        .iter()
+        .filter_map(|p| {
+            let item = p.beckn_structure.as_ref()?;
+            parse_payload_consent(&p.profile_id, item, path)
+        })
+        .collect()
+}
+
+pub async fn handle_consent_feed(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Json(req): Json<ConsentFeedRequest>,
+) -> Result<Json<Value>, ApiError> {
+    if req.consents.iter().any(|c| c.profile_id.trim().is_empty()) {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "Every consent entry needs a profile_id",
+        ));
+    }
+
+    let received = req.consents.len();
+    let consents: Vec<NewConsent> = req
+        .consents
+        .into_iter()
+        .map(|c| NewConsent {
+            profile_id: c.profile_id,
+            purposes: c.purposes,
+            granted_at: c.granted_at,
+            expires_at: c.expires_at,
+            withdrawn: c.withdrawn,
+        })
+        .collect();
+
+    match upsert_consents(&app_state.db_pool, &consents, "feed").await {
+        Ok(applied) => {
+            info!(
+                "📝 Consent feed: {} received, {} applied (caller={})",
+                received, applied, caller.id
+            );
+            Ok(Json(serde_json::json!({
+                "received": received,
+                "applied": applied
+            })))
+        }
+        Err(e) => {
+            error!("upsert_consents failed: {:?}", e);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to store consent",
+            ))
+        }
+    }
+}
+
+pub async fn handle_get_consent(
+    State(app_state): State<Arc<AppState>>,
+    Path(profile_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    match get_consent(&app_state.db_pool, &profile_id).await {
+        Ok(Some(consent)) => Ok(Json(serde_json::json!(consent))),
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            "No consent recorded for this profile",
+        )),
+        Err(e) => {
+            error!("get_consent failed: {:?}", e);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to read consent",
+            ))
+        }
+    }
+}
+
+pub async fn handle_erase_profile(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(profile_id): Path<String>,
+    body: Option<Json<ErasureRequest>>,
+) -> Result<Json<Value>, ApiError> {
+    let req = body.map(|Json(r)| r).unwrap_or_default();
+
+    match erase_profile(
+        &app_state.db_pool,
+        &profile_id,
+        &caller.id,
+        req.reason.as_deref(),
+    )
+    .await
+    {
+        Ok(result) => {
+            info!(
+                "🗑️ Profile erased: profile_id={}, rows={}, by={}",
+                profile_id, result.profiles_deleted, caller.id
+            );
+            Ok(Json(serde_json::json!(result)))
+        }
+        Err(e) => {
+            error!("erase_profile failed: {:?}", e);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to erase profile",
+            ))
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use crate::db::profile::{count_talent_matches, stream_export_rows, ExportRow, TalentFilter};
+use crate::models::auth::Caller;
+use crate::models::export::{ExportFormat, ExportQuery};
+use crate::models::profiles::ProfileSearchRequest;
+use crate::models::search::TalentSearchRequest;
+use crate::services::profile::build_talent_search_params;
+use crate::state::AppState;
+use crate::utils::audit::{record_access, ClientIp};
+use crate::utils::redaction::RedactionView;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use futures::StreamExt;
+use rust_xlsxwriter::{Format, Workbook};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{error, info};
+
+const CSV_CHUNK_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ExportColumn {
+    ProfileId,
+    Name,
+    Role,
+    Location,
+    WorkExperience,
+    WorkExperienceYears,
+    LastRoleHeld,
+    Qualification,
+    JobRolesInterestedIn,
+    JobsInterestedIn,
+    BppId,
+    BppUri,
+    TransactionId,
+    UpdatedAt,
+}
+
+const ALL_COLUMNS: [ExportColumn; 14] = [
+    ExportColumn::ProfileId,
+    ExportColumn::Name,
+    ExportColumn::Role,
+    ExportColumn::Location,
+    ExportColumn::WorkExperience,
+    ExportColumn::WorkExperienceYears,
+    ExportColumn::LastRoleHeld,
+    ExportColumn::Qualification,
+    ExportColumn::JobRolesInterestedIn,
+    ExportColumn::JobsInterestedIn,
+    ExportColumn::BppId,
+    ExportColumn::BppUri,
+    ExportColumn::TransactionId,
+    ExportColumn::UpdatedAt,
+];
+
+impl ExportColumn {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportColumn::ProfileId => "profile_id",
+            ExportColumn::Name => "name",
+            ExportColumn::Role => "role",
+            ExportColumn::Location => "location",
+            ExportColumn::WorkExperience => "work_experience",
+            ExportColumn::WorkExperienceYears => "work_experience_years",
+            ExportColumn::LastRoleHeld => "last_role_held",
+            ExportColumn::Qualification => "qualification",
+            ExportColumn::JobRolesInterestedIn => "job_roles_interested_in",
+            ExportColumn::JobsInterestedIn => "jobs_interested_in",
+            ExportColumn::BppId => "bpp_id",
+            ExportColumn::BppUri => "bpp_uri",
+            ExportColumn::TransactionId => "transaction_id",
+            ExportColumn::UpdatedAt => "updated_at",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        ALL_COLUMNS.iter().copied().find(|c| c.name() == name)
+    }
+
+    pub fn value(&self, row: &ExportRow) -> String {
+        let c = &row.candidate;
+        match self {
+            ExportColumn::ProfileId => c.profile_id.clone(),
+            ExportColumn::Name => c.name.clone().unwrap_or_default(),
+            ExportColumn::Role => c.role.clone().unwrap_or_default(),
+            ExportColumn::Location => c.location.clone().unwrap_or_default(),
+            ExportColumn::WorkExperience => c.work_experience.clone().unwrap_or_default(),
+            ExportColumn::WorkExperienceYears => {
+                c.work_experience_years.clone().unwrap_or_default()
+            }
+            ExportColumn::LastRoleHeld => c.last_role_held.clone().unwrap_or_default(),
+            ExportColumn::Qualification => c.qualification.clone().unwrap_or_default(),
+            ExportColumn::JobRolesInterestedIn => {
+                c.job_roles_interested_in.clone().unwrap_or_default()
+            }
+            ExportColumn::JobsInterestedIn => c
+                .jobs_interested_in
+                .as_ref()
+                .map(|j| j.join("; "))
+                .unwrap_or_default(),
+            ExportColumn::BppId => row.bpp_id.clone(),
+            ExportColumn::BppUri => row.bpp_uri.clone(),
+            ExportColumn::TransactionId => row.transaction_id.clone(),
+            ExportColumn::UpdatedAt => row.updated_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
+
+fn bad_request(message: String) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": message,
+            "available_columns": ALL_COLUMNS.iter().map(|c| c.name()).collect::<Vec<_>>(),
+        })),
+    )
+}
+
+fn resolve_columns(
+    requested: Option<&str>,
+    defaults: &[String],
+) -> Result<Vec<ExportColumn>, (StatusCode, Json<Value>)> {
+    let names: Vec<&str> = match requested {
+        Some(cols) => cols
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .collect(),
+        None => defaults.iter().map(String::as_str).collect(),
+    };
+
+    if names.is_empty() {
+        return Ok(ALL_COLUMNS.to_vec());
+    }
+
+    names
+        .into_iter()
+        .map(|n| ExportColumn::parse(n).ok_or_else(|| bad_request(format!("Unknown column: {n}"))))
+        .collect()
+}
+
+/// Who asked for an export; written to the access log once the rows that
+/// were actually sent are known.
+struct ExportAudit {
+    caller: Caller,
+    client_ip: ClientIp,
+    action: &'static str,
+    query: Option<Value>,
+}
+
+impl ExportAudit {
+    async fn record(self, app_state: &AppState, profile_ids: Vec<String>) {
+        record_access(
+            app_state,
+            &self.caller,
+            &self.client_ip,
+            self.action,
+            profile_ids,
+            self.query,
+        )
+        .await;
+    }
+}
+
+fn encode_csv_record<I, T>(fields: I) -> Result<Vec<u8>, csv::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+    writer
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))
+}
+
+fn csv_response(
+    app_state: &Arc<AppState>,
+    filter: TalentFilter,
+    view: RedactionView,
+    columns: Vec<ExportColumn>,
+    audit: ExportAudit,
+) -> Response {
+    let (tx, mut rx) = mpsc::channel::<Result<Bytes, std::io::Error>>(16);
+    let producer_state = app_state.clone();
+
+    // Tracked so shutdown lets a running export finish and audit its rows.
+    app_state.tasks.spawn(async move {
+        let profile_ids = write_csv(&producer_state, filter, view, &columns, tx).await;
+        audit.record(&producer_state, profile_ids).await;
+    });
+
+    let body = Body::from_stream(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)));
+    let filename = format!("talent-export-{}.csv", Utc::now().format("%Y%m%dT%H%M%S"));
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Streams CSV chunks into `tx` and returns the profile ids that were
+/// encoded, so the export can be audited however the stream ended.
+async fn write_csv(
+    app_state: &AppState,
+    filter: TalentFilter,
+    view: RedactionView,
+    columns: &[ExportColumn],
+    tx: mpsc::Sender<Result<Bytes, std::io::Error>>,
+) -> Vec<String> {
+    let mut profile_ids = Vec::new();
+    let mut chunk = match encode_csv_record(columns.iter().map(|c| c.name())) {
+        Ok(header) => header,
+        Err(e) => {
+            let _ = tx.send(Err(std::io::Error::other(e))).await;
+            return profile_ids;
+        }
+    };
+
+    let max_rows = app_state.config.current().export.max_rows;
+    let mut rows = Box::pin(stream_export_rows(
+        &app_state.db_pool,
+        filter,
+        view,
+        max_rows,
+    ));
+
+    while let Some(row) = rows.next().await {
+        let encoded = row.map_err(std::io::Error::other).and_then(|row| {
+            encode_csv_record(columns.iter().map(|c| c.value(&row)))
+                .map(|line| (row.candidate.profile_id, line))
+                .map_err(std::io::Error::other)
+        });
+
+        match encoded {
+            Ok((profile_id, line)) => {
+                chunk.extend_from_slice(&line);
+                profile_ids.push(profile_id);
+            }
+            Err(e) => {
+                error!("CSV export failed after {} rows: {}", profile_ids.len(), e);
+                let _ = tx.send(Err(e)).await;
+                return profile_ids;
+            }
+        }
+
+        if chunk.len() >= CSV_CHUNK_BYTES {
+            let full = std::mem::take(&mut chunk);
+            if tx.send(Ok(Bytes::from(full))).await.is_err() {
+                info!(
+                    "CSV export cancelled by client after {} rows",
+                    profile_ids.len()
+                );
+                return profile_ids;
+            }
+        }
+    }
+
+    if !chunk.is_empty() {
+        let _ = tx.send(Ok(Bytes::from(chunk))).await;
+    }
+    info!("CSV export finished: {} rows", profile_ids.len());
+    profile_ids
+}
+
+fn build_xlsx(
+    columns: &[ExportColumn],
+    mut rx: mpsc::Receiver<Result<ExportRow, sqlx::Error>>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    worksheet.set_name("Candidates")?;
+
+    for (col, column) in columns.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, column.name(), &bold)?;
+    }
+
+    let mut row_num: u32 = 1;
+    while let Some(row) = rx.blocking_recv() {
+        let row = row?;
+        for (col, column) in columns.iter().enumerate() {
+            worksheet.write_string(row_num, col as u16, column.value(&row))?;
+        }
+        row_num += 1;
+    }
+    info!("XLSX export finished: {} rows", row_num - 1);
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+async fn xlsx_response(
+    app_state: &Arc<AppState>,
+    filter: TalentFilter,
+    view: RedactionView,
+    columns: Vec<ExportColumn>,
+    audit: ExportAudit,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    // The workbook is held in memory until it is sent, so an export over
+    // the limit is refused up front rather than cut off silently.
+    let max_rows = app_state.config.current().export.max_rows;
+    let matched = count_talent_matches(&app_state.db_pool, &filter)
+        .await
+        .map_err(|e| {
+            error!("count_talent_matches failed: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": "Failed to export profiles"
+                })),
+            )
+        })?;
+    if matched as u64 > max_rows {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": format!(
+                    "{matched} profiles match, above the XLSX export limit of {max_rows}; narrow the filter or export CSV"
+                ),
+                "matched_count": matched,
+                "max_rows": max_rows,
+            })),
+        ));
+    }
+
+    let (tx, rx) = mpsc::channel::<Result<ExportRow, sqlx::Error>>(256);
+    let feeder_state = app_state.clone();
+
+    let feeder = app_state.tasks.spawn(async move {
+        let mut rows = Box::pin(stream_export_rows(
+            &feeder_state.db_pool,
+            filter,
+            view,
+            max_rows,
+        ));
+        let mut profile_ids = Vec::new();
+        while let Some(row) = rows.next().await {
+            let failed = row.is_err();
+            if let Ok(row) = &row {
+                profile_ids.push(row.candidate.profile_id.clone());
+            }
+            if tx.send(row).await.is_err() || failed {
+                break;
+            }
+        }
+        profile_ids
+    });
+
+    let buffer = app_state
+        .tasks
+        .spawn_blocking(move || build_xlsx(&columns, rx))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|r| r);
+
+    // Only rows that made it into a delivered workbook count as accessed.
+    let profile_ids = match (&buffer, feeder.await) {
+        (Ok(_), Ok(ids)) => ids,
+        _ => Vec::new(),
+    };
+    if buffer.is_ok() {
+        audit.record(app_state, profile_ids).await;
+    }
+
+    match buffer {
+        Ok(bytes) => {
+            let filename = format!("talent-export-{}.xlsx", Utc::now().format("%Y%m%dT%H%M%S"));
+            Ok((
+                [
+                    (
+                        header::CONTENT_TYPE,
+                        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+                            .to_string(),
+                    ),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"{filename}\""),
+                    ),
+                ],
+                bytes,
+            )
+                .into_response())
+        }
+        Err(e) => {
+            error!("XLSX export failed: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": "Failed to export profiles"
+                })),
+            ))
+        }
+    }
+}
+
+async fn export(
+    app_state: &Arc<AppState>,
+    filter: TalentFilter,
+    query: ExportQuery,
+    audit: ExportAudit,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let columns = resolve_columns(
+        query.columns.as_deref(),
+        &app_state.config.current().export.default_columns,
+    )?;
+    let format = query.format.unwrap_or_default();
+    let view = RedactionView::new(&app_state.config.current().redaction, audit.caller.role);
+
+    info!(
+        "Exporting profiles: format={:?}, columns={}, trade={:?}, location={:?}, experience={:?}",
+        format,
+        columns.len(),
+        filter.trade,
+        filter.location,
+        filter.experience
+    );
+
+    match format {
+        ExportFormat::Csv => Ok(csv_response(app_state, filter, view, columns, audit)),
+        ExportFormat::Xlsx => xlsx_response(app_state, filter, view, columns, audit).await,
+    }
+}
+
+pub async fn handle_talent_search_export(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    client_ip: ClientIp,
+    Query(query): Query<ExportQuery>,
+    Json(req): Json<TalentSearchRequest>,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let params =
+        build_talent_search_params(&req, app_state.config.current().consent.search_purpose());
+    let filter = TalentFilter {
+        trade: params.trade,
+        location: params.location,
+        experience: params.experience,
+        consent_purpose: params.consent_purpose,
+    };
+    let audit = ExportAudit {
+        caller,
+        client_ip,
+        action: "talent_search_export",
+        query: Some(serde_json::json!({ "request": req, "export": query })),
+    };
+
+    export(&app_state, filter, query, audit).await
+}
+
+pub async fn handle_profiles_export(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    client_ip: ClientIp,
+    Query(query): Query<ExportQuery>,
+    Json(req): Json<ProfileSearchRequest>,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let audit = ExportAudit {
+        caller,
+        client_ip,
+        action: "profiles_export",
+        query: Some(serde_json::json!({ "request": req, "export": query })),
+    };
+
+    let filter = TalentFilter {
+        consent_purpose: app_state
+            .config
+            .current()
+            .consent
+            .search_purpose()
+            .map(str::to_string),
+        ..TalentFilter::default()
+    };
+
+    export(&app_state, filter, query, audit).await
+}
@@ -0,0 +1,189 @@
+use crate::models::health::{ComponentHealth, ComponentState, ReadinessResponse};
+use crate::models::webhook::HealthResponse;
+use crate::state::AppState;
+
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use deadpool_redis::Pool;
+use redis::AsyncCommands;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+use tracing::{error, warn};
+
+/// When the last profile sync finished cleaning up stale rows, as RFC 3339.
+pub const LAST_SYNC_KEY: &str = "sync:last_completed_at";
+
+pub async fn mark_sync_completed(redis_pool: &Pool) {
+    let result = async {
+        let mut redis = redis_pool.get().await?;
+        let _: () = redis.set(LAST_SYNC_KEY, Utc::now().to_rfc3339()).await?;
+        anyhow::Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        error!("Failed to record sync completion: {:?}", e);
+    }
+}
+
+async fn last_sync_completed(redis_pool: &Pool) -> anyhow::Result<Option<DateTime<Utc>>> {
+    let mut redis = redis_pool.get().await?;
+    let value: Option<String> = redis.get(LAST_SYNC_KEY).await?;
+    Ok(value
+        .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+        .map(|t| t.with_timezone(&Utc)))
+}
+
+fn component(status: ComponentState, required: bool) -> ComponentHealth {
+    ComponentHealth {
+        status,
+        required,
+        latency_ms: None,
+        error: None,
+        last_completed_at: None,
+        age_seconds: None,
+    }
+}
+
+/// Runs `check` under `limit`, reporting its latency and any failure.
+async fn probe<F>(limit: Duration, required: bool, check: F) -> ComponentHealth
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    let started = Instant::now();
+    let result = match timeout(limit, check).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::anyhow!("timed out after {}ms", limit.as_millis())),
+    };
+
+    let mut health = component(ComponentState::Ok, required);
+    health.latency_ms = Some(started.elapsed().as_millis() as u64);
+    if let Err(e) = result {
+        health.status = ComponentState::Down;
+        health.error = Some(e.to_string());
+    }
+    health
+}
+
+async fn check_db(app_state: &AppState) -> anyhow::Result<()> {
+    sqlx::query("SELECT 1").execute(&app_state.db_pool).await?;
+    Ok(())
+}
+
+async fn check_redis(app_state: &AppState) -> anyhow::Result<()> {
+    let mut redis = app_state.redis_pool.get().await?;
+    let _: String = redis::cmd("PING").query_async(&mut redis).await?;
+    Ok(())
+}
+
+/// Any HTTP response counts as reachable; only transport errors fail.
+async fn check_provider_db(app_state: &AppState, limit: Duration) -> anyhow::Result<()> {
+    let url = app_state.config.current().provider_db.db_uri.clone();
+    reqwest::Client::builder()
+        .timeout(limit)
+        .build()?
+        .get(url)
+        .send()
+        .await?;
+    Ok(())
+}
+
+async fn check_sync(app_state: &AppState, limit: Duration) -> ComponentHealth {
+    let max_age = app_state.config.current().health.sync_max_age_seconds;
+    let required = max_age.is_some();
+
+    let last = match timeout(limit, last_sync_completed(&app_state.redis_pool)).await {
+        Ok(Ok(last)) => last,
+        Ok(Err(e)) => {
+            let mut health = component(ComponentState::Unknown, required);
+            health.error = Some(e.to_string());
+            return health;
+        }
+        Err(_) => {
+            let mut health = component(ComponentState::Unknown, required);
+            health.error = Some(format!("timed out after {}ms", limit.as_millis()));
+            return health;
+        }
+    };
+
+    // No sync has finished yet. A fresh deployment needs to receive
+    // on_search callbacks to complete one, so this does not block readiness.
+    let Some(last) = last else {
+        return component(ComponentState::Unknown, false);
+    };
+
+    let age = (Utc::now() - last).num_seconds();
+    let stale = max_age.is_some_and(|max| age > max as i64);
+    let mut health = component(
+        if stale {
+            ComponentState::Stale
+        } else {
+            ComponentState::Ok
+        },
+        required,
+    );
+    health.last_completed_at = Some(last.to_rfc3339());
+    health.age_seconds = Some(age);
+    health
+}
+
+/// Liveness: the process is up and serving requests.
+pub async fn handle_liveness() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "OK",
+        timestamp: Utc::now().to_rfc3339(),
+    })
+}
+
+/// Readiness: Postgres, Redis and the provider DB are reachable and the
+/// profile sync is fresh. Returns 503 when any required component fails.
+pub async fn handle_readiness(
+    State(app_state): State<Arc<AppState>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let health = app_state.config.current().health.clone();
+    let limit = Duration::from_millis(health.check_timeout_ms);
+
+    let (db, redis, provider_db, sync) = tokio::join!(
+        probe(limit, true, check_db(&app_state)),
+        probe(limit, true, check_redis(&app_state)),
+        probe(
+            limit,
+            health.require_provider_db,
+            check_provider_db(&app_state, limit)
+        ),
+        check_sync(&app_state, limit),
+    );
+
+    let components = BTreeMap::from([
+        ("db", db),
+        ("redis", redis),
+        ("provider_db", provider_db),
+        ("sync", sync),
+    ]);
+
+    let ready = !components.values().any(ComponentHealth::is_blocking);
+    if !ready {
+        let failing: Vec<&str> = components
+            .iter()
+            .filter(|(_, c)| c.is_blocking())
+            .map(|(name, _)| *name)
+            .collect();
+        warn!("Not ready: {}", failing.join(", "));
+    }
+
+    (
+        if ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        },
+        Json(ReadinessResponse {
+            status: if ready { "ready" } else { "not_ready" },
+            timestamp: Utc::now().to_rfc3339(),
+            components,
+        }),
+    )
+}
@@ -0,0 +1,594 @@
+use crate::db::profile::{fetch_match_pool, redacted_profile, SampleCandidate};
+use crate::models::auth::Caller;
+use crate::models::matching::{
+    CriterionScore, MatchCriterion, MatchedCandidate, TalentMatchRequest, TalentMatchResponse,
+};
+use crate::state::AppState;
+use crate::utils::audit::{record_access, ClientIp};
+use crate::utils::redaction::RedactionView;
+
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+
+const MATCH_POOL_LIMIT: u32 = 2000;
+
+const ROLE_WEIGHT: f64 = 0.35;
+const LOCATION_WEIGHT: f64 = 0.20;
+const EXPERIENCE_WEIGHT: f64 = 0.15;
+const QUALIFICATION_WEIGHT: f64 = 0.10;
+const JOB_TYPE_WEIGHT: f64 = 0.10;
+const PAY_WEIGHT: f64 = 0.10;
+
+/// Experience score lost per year short of the minimum or over the maximum.
+const EXPERIENCE_SHORTFALL_DECAY: f64 = 0.25;
+const EXPERIENCE_EXCESS_DECAY: f64 = 0.1;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Candidate attributes the scorer looks at, derived from the normalized
+/// `SampleCandidate` plus the few raw fields it does not carry. Built from
+/// the caller's redacted view, so nothing hidden from them affects ranking.
+pub struct CandidateFeatures {
+    pub candidate: SampleCandidate,
+    pub gps: Option<(f64, f64)>,
+    pub expected_pay: Option<f64>,
+}
+
+impl CandidateFeatures {
+    pub fn from_beckn(profile_id: String, beckn: &Value, view: &RedactionView) -> Self {
+        let redacted = redacted_profile(Some(beckn), view);
+        let profile = redacted.as_ref();
+        let candidate = SampleCandidate::from_profile(profile_id, profile);
+
+        let gps = ["locationdata", "location"]
+            .iter()
+            .filter_map(|key| profile.and_then(|p| p.get(*key)).and_then(|l| l.get("gps")))
+            .find_map(parse_gps);
+
+        let expected_pay = profile
+            .and_then(|p| p.get("whatIWant"))
+            .and_then(|w| {
+                ["expectedSalary", "expectedPay", "salaryExpectation"]
+                    .iter()
+                    .find_map(|key| w.get(*key))
+            })
+            .and_then(parse_number);
+
+        CandidateFeatures {
+            candidate,
+            gps,
+            expected_pay,
+        }
+    }
+}
+
+fn parse_gps(value: &Value) -> Option<(f64, f64)> {
+    match value {
+        Value::String(s) => {
+            let (lat, lng) = s.split_once(',')?;
+            Some((lat.trim().parse().ok()?, lng.trim().parse().ok()?))
+        }
+        Value::Object(o) => Some((
+            o.get("lat").and_then(parse_number)?,
+            o.get("lng")
+                .or_else(|| o.get("lon"))
+                .and_then(parse_number)?,
+        )),
+        _ => None,
+    }
+}
+
+fn parse_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => first_number(s),
+        _ => None,
+    }
+}
+
+fn first_number(s: &str) -> Option<f64> {
+    let cleaned: String = s.chars().filter(|c| *c != ',').collect();
+    let start = cleaned.find(|c: char| c.is_ascii_digit())?;
+    let digits: String = cleaned[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    digits.parse().ok()
+}
+
+fn contains_ci(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn normalize_job_type(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn haversine_km(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat1, lng1) = (a.0.to_radians(), a.1.to_radians());
+    let (lat2, lng2) = (b.0.to_radians(), b.1.to_radians());
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lng2 - lng1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
+fn experience_years(candidate: &SampleCandidate) -> Option<f64> {
+    if let Some(years) = candidate
+        .work_experience_years
+        .as_deref()
+        .and_then(first_number)
+    {
+        return Some(years);
+    }
+    match candidate.work_experience.as_deref() {
+        Some(exp) if contains_ci(exp, "fresher") => Some(0.0),
+        Some(exp) => first_number(exp),
+        None => None,
+    }
+}
+
+fn qualification_level(s: &str) -> Option<u8> {
+    let lower = s.to_lowercase();
+    let is_iti = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|token| token == "iti");
+
+    if lower.contains("college") || lower.contains("graduate") || lower.contains("degree") {
+        Some(3)
+    } else if is_iti || lower.contains("certification") || lower.contains("diploma") {
+        Some(2)
+    } else if lower.contains("school") {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+fn score_role(role: &str, c: &SampleCandidate) -> (f64, String) {
+    if c.role.as_deref().is_some_and(|r| contains_ci(r, role)) {
+        (1.0, format!("current role matches '{role}'"))
+    } else if c
+        .job_roles_interested_in
+        .as_deref()
+        .is_some_and(|r| contains_ci(r, role))
+    {
+        (1.0, format!("interested in '{role}' roles"))
+    } else if c
+        .last_role_held
+        .as_deref()
+        .is_some_and(|r| contains_ci(r, role))
+    {
+        (0.75, format!("last role held matches '{role}'"))
+    } else {
+        (0.0, format!("no role match for '{role}'"))
+    }
+}
+
+fn score_location(job: &TalentMatchRequest, f: &CandidateFeatures) -> (f64, String) {
+    if let (Some(lat), Some(lng), Some(gps)) = (job.lat, job.lng, f.gps) {
+        let radius = job.radius_km.unwrap_or(10.0).max(0.1);
+        let distance = haversine_km((lat, lng), gps);
+        let score = if distance <= radius {
+            1.0
+        } else {
+            (1.0 - (distance - radius) / radius).max(0.0)
+        };
+        return (
+            score,
+            format!("{distance:.1} km away (radius {radius:.1} km)"),
+        );
+    }
+
+    match (job.location.as_deref(), f.candidate.location.as_deref()) {
+        (Some(wanted), Some(actual))
+            if contains_ci(actual, wanted) || contains_ci(wanted, actual) =>
+        {
+            (1.0, format!("located in {actual}"))
+        }
+        (Some(_), Some(actual)) => (0.0, format!("located in {actual}")),
+        _ => (0.0, "location not specified".to_string()),
+    }
+}
+
+fn score_experience(job: &TalentMatchRequest, c: &SampleCandidate) -> (f64, String) {
+    let Some(years) = experience_years(c) else {
+        return (0.0, "experience not specified".to_string());
+    };
+    let min = job.experience_min.unwrap_or(0.0);
+    let max = job.experience_max.unwrap_or(f64::MAX);
+
+    let score = if years < min {
+        (1.0 - (min - years) * EXPERIENCE_SHORTFALL_DECAY).max(0.0)
+    } else if years > max {
+        (1.0 - (years - max) * EXPERIENCE_EXCESS_DECAY).max(0.0)
+    } else {
+        1.0
+    };
+    (score, format!("{years} years of experience"))
+}
+
+fn score_qualification(required: &str, c: &SampleCandidate) -> (f64, String) {
+    let Some(actual) = c.qualification.as_deref() else {
+        return (0.0, "qualification not specified".to_string());
+    };
+
+    let score = match (qualification_level(required), qualification_level(actual)) {
+        (Some(req), Some(have)) if have >= req => 1.0,
+        (Some(_), Some(_)) => 0.0,
+        _ if contains_ci(actual, required) => 1.0,
+        _ => 0.0,
+    };
+    (score, format!("highest qualification: {actual}"))
+}
+
+fn score_job_type(job_type: &str, c: &SampleCandidate) -> (f64, String) {
+    let wanted = normalize_job_type(job_type);
+    let Some(types) = c.jobs_interested_in.as_ref().filter(|t| !t.is_empty()) else {
+        return (0.0, "job type preference not specified".to_string());
+    };
+    let normalized: Vec<String> = types.iter().map(|t| normalize_job_type(t)).collect();
+
+    let score = if normalized.contains(&wanted) {
+        1.0
+    } else if normalized.iter().any(|t| t == "flexible") {
+        0.75
+    } else {
+        0.0
+    };
+    (score, format!("interested in {}", types.join(", ")))
+}
+
+fn score_pay(job: &TalentMatchRequest, f: &CandidateFeatures) -> (f64, String) {
+    let Some(expected) = f.expected_pay else {
+        return (0.0, "expected pay not specified".to_string());
+    };
+    let Some(offered) = job.pay_max.or(job.pay_min).filter(|p| *p > 0.0) else {
+        return (1.0, format!("expects {expected}"));
+    };
+
+    let score = if expected <= offered {
+        1.0
+    } else {
+        (1.0 - (expected - offered) / offered).max(0.0)
+    };
+    (
+        score,
+        format!("expects {expected}, offered up to {offered}"),
+    )
+}
+
+pub fn has_criteria(job: &TalentMatchRequest) -> bool {
+    job.role.is_some()
+        || job.location.is_some()
+        || (job.lat.is_some() && job.lng.is_some())
+        || job.experience_min.is_some()
+        || job.experience_max.is_some()
+        || job.qualification.is_some()
+        || job.job_type.is_some()
+        || job.pay_min.is_some()
+        || job.pay_max.is_some()
+}
+
+/// Scores one candidate against a job. Only criteria present on the job take
+/// part, and their weights are renormalized so the result is always 0..=100.
+pub fn score_candidate(
+    job: &TalentMatchRequest,
+    f: &CandidateFeatures,
+) -> (f64, Vec<CriterionScore>) {
+    let c = &f.candidate;
+    let mut breakdown = Vec::new();
+
+    let mut push = |criterion, weight, (score, explanation): (f64, String)| {
+        breakdown.push(CriterionScore {
+            criterion,
+            weight,
+            score,
+            explanation,
+        });
+    };
+
+    if let Some(role) = job.role.as_deref() {
+        push(MatchCriterion::Role, ROLE_WEIGHT, score_role(role, c));
+    }
+    if job.location.is_some() || (job.lat.is_some() && job.lng.is_some()) {
+        push(
+            MatchCriterion::Location,
+            LOCATION_WEIGHT,
+            score_location(job, f),
+        );
+    }
+    if job.experience_min.is_some() || job.experience_max.is_some() {
+        push(
+            MatchCriterion::Experience,
+            EXPERIENCE_WEIGHT,
+            score_experience(job, c),
+        );
+    }
+    if let Some(qualification) = job.qualification.as_deref() {
+        push(
+            MatchCriterion::Qualification,
+            QUALIFICATION_WEIGHT,
+            score_qualification(qualification, c),
+        );
+    }
+    if let Some(job_type) = job.job_type.as_deref() {
+        push(
+            MatchCriterion::JobType,
+            JOB_TYPE_WEIGHT,
+            score_job_type(job_type, c),
+        );
+    }
+    if job.pay_min.is_some() || job.pay_max.is_some() {
+        push(MatchCriterion::Pay, PAY_WEIGHT, score_pay(job, f));
+    }
+
+    let total_weight: f64 = breakdown.iter().map(|b| b.weight).sum();
+    if total_weight == 0.0 {
+        return (0.0, breakdown);
+    }
+    let weighted: f64 = breakdown.iter().map(|b| b.weight * b.score).sum();
+    let score = (weighted / total_weight * 1000.0).round() / 10.0;
+
+    (score, breakdown)
+}
+
+/// Ranks candidates by score (descending), breaking ties on `profile_id` so
+/// the same pool and job always produce the same order.
+pub fn rank_candidates(
+    job: &TalentMatchRequest,
+    pool: Vec<CandidateFeatures>,
+) -> Vec<MatchedCandidate> {
+    let min_score = job.min_score.unwrap_or(0.0);
+
+    let mut ranked: Vec<MatchedCandidate> = pool
+        .into_iter()
+        .map(|f| {
+            let (score, breakdown) = score_candidate(job, &f);
+            MatchedCandidate {
+                profile_id: f.candidate.profile_id.clone(),
+                score,
+                candidate: f.candidate,
+                breakdown,
+            }
+        })
+        .filter(|m| m.score > 0.0 && m.score >= min_score)
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.profile_id.cmp(&b.profile_id))
+    });
+
+    ranked
+}
+
+pub async fn handle_talent_match(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    client_ip: ClientIp,
+    Json(req): Json<TalentMatchRequest>,
+) -> Result<Json<TalentMatchResponse>, (StatusCode, Json<Value>)> {
+    if !has_criteria(&req) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "At least one matching criterion is required"
+            })),
+        ));
+    }
+
+    let page = req.page.unwrap_or(1).max(1);
+    let limit = req.limit.unwrap_or(10).clamp(1, 100);
+
+    info!(
+        "Matching talent: role={:?}, location={:?}, radius_km={:?}, page={}, limit={}",
+        req.role, req.location, req.radius_km, page, limit
+    );
+
+    let config = app_state.config.current();
+    let view = RedactionView::new(&config.redaction, caller.role);
+    // One extra row tells whether the pool was cut off.
+    let mut rows = match fetch_match_pool(
+        &app_state.db_pool,
+        config.consent.search_purpose(),
+        MATCH_POOL_LIMIT + 1,
+    )
+    .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            tracing::error!("fetch_match_pool failed: {:?}", err);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": "Failed to match talent"
+                })),
+            ));
+        }
+    };
+
+    let truncated = rows.len() > MATCH_POOL_LIMIT as usize;
+    rows.truncate(MATCH_POOL_LIMIT as usize);
+    let pool_size = rows.len();
+    let pool = rows
+        .into_iter()
+        .map(|r| CandidateFeatures::from_beckn(r.profile_id, &r.beckn_structure, &view))
+        .collect();
+
+    let ranked = rank_candidates(&req, pool);
+    let matched_count = ranked.len();
+    let results: Vec<MatchedCandidate> = ranked
+        .into_iter()
+        .skip((page as usize - 1).saturating_mul(limit as usize))
+        .take(limit as usize)
+        .collect();
+
+    record_access(
+        &app_state,
+        &caller,
+        &client_ip,
+        "talent_match",
+        results.iter().map(|c| c.profile_id.clone()).collect(),
+        serde_json::to_value(&req).ok(),
+    )
+    .await;
+
+    Ok(Json(TalentMatchResponse {
+        pool_size,
+        matched_count,
+        truncated,
+        results,
+        page,
+        limit,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedactionConfig;
+    use crate::models::auth::CallerRole;
+    use serde_json::json;
+
+    fn features(profile_id: &str, profile: Value) -> CandidateFeatures {
+        CandidateFeatures::from_beckn(
+            profile_id.to_string(),
+            &json!({ "tags": { "profile": profile } }),
+            &RedactionView::new(&RedactionConfig::default(), CallerRole::Privileged),
+        )
+    }
+
+    #[test]
+    fn haversine_km_measures_great_circle_distance() {
+        assert_eq!(haversine_km((12.97, 77.59), (12.97, 77.59)), 0.0);
+        // Bengaluru to Chennai is about 290 km.
+        let d = haversine_km((12.9716, 77.5946), (13.0827, 80.2707));
+        assert!((d - 290.0).abs() < 5.0, "got {d}");
+        // A quarter of the equator.
+        let quarter = haversine_km((0.0, 0.0), (0.0, 90.0));
+        assert!((quarter - EARTH_RADIUS_KM * std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn score_candidate_renormalizes_present_criteria() {
+        let job = TalentMatchRequest {
+            role: Some("electrician".into()),
+            ..Default::default()
+        };
+        let f = features("p1", json!({ "role": "Electrician" }));
+        let (score, breakdown) = score_candidate(&job, &f);
+        assert_eq!(score, 100.0);
+        assert_eq!(breakdown.len(), 1);
+        assert_eq!(breakdown[0].criterion, MatchCriterion::Role);
+    }
+
+    #[test]
+    fn score_candidate_weights_partial_matches() {
+        let job = TalentMatchRequest {
+            role: Some("electrician".into()),
+            experience_min: Some(3.0),
+            ..Default::default()
+        };
+        let f = features(
+            "p1",
+            json!({
+                "role": "Plumber",
+                "whatIHave": { "workExperienceYears": "1 year" }
+            }),
+        );
+        let (score, breakdown) = score_candidate(&job, &f);
+        // Role misses; experience is two years short: 1 - 2 * 0.25 = 0.5.
+        let expected = (EXPERIENCE_WEIGHT * 0.5) / (ROLE_WEIGHT + EXPERIENCE_WEIGHT) * 100.0;
+        assert!((score - expected).abs() < 0.1, "got {score}");
+        assert_eq!(breakdown[0].score, 0.0);
+        assert_eq!(breakdown[1].score, 0.5);
+    }
+
+    #[test]
+    fn score_location_uses_radius_when_gps_is_known() {
+        let job = TalentMatchRequest {
+            lat: Some(12.9716),
+            lng: Some(77.5946),
+            radius_km: Some(5.0),
+            ..Default::default()
+        };
+        let near = features("near", json!({ "locationdata": { "gps": "12.98,77.60" } }));
+        let far = features(
+            "far",
+            json!({ "locationdata": { "gps": "13.0827,80.2707" } }),
+        );
+        assert_eq!(score_candidate(&job, &near).0, 100.0);
+        assert_eq!(score_candidate(&job, &far).0, 0.0);
+    }
+
+    #[test]
+    fn rank_candidates_orders_by_score_then_profile_id_and_drops_zero() {
+        let job = TalentMatchRequest {
+            role: Some("electrician".into()),
+            job_type: Some("Full Time".into()),
+            ..Default::default()
+        };
+        let pool = vec![
+            features("b", json!({ "role": "Electrician" })),
+            features(
+                "c",
+                json!({
+                    "role": "Electrician",
+                    "whatIWant": { "natureOfJobsInterestedIn": ["full-time"] }
+                }),
+            ),
+            features("a", json!({ "role": "Electrician" })),
+            features("z", json!({ "role": "Plumber" })),
+        ];
+        let ids: Vec<String> = rank_candidates(&job, pool)
+            .into_iter()
+            .map(|m| m.profile_id)
+            .collect();
+        assert_eq!(ids, ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn rank_candidates_applies_min_score() {
+        let job = TalentMatchRequest {
+            role: Some("electrician".into()),
+            experience_min: Some(3.0),
+            min_score: Some(90.0),
+            ..Default::default()
+        };
+        let pool = vec![
+            features(
+                "senior",
+                json!({ "role": "Electrician", "whatIHave": { "workExperienceYears": "5" } }),
+            ),
+            features(
+                "junior",
+                json!({ "role": "Electrician", "whatIHave": { "workExperienceYears": "1" } }),
+            ),
+        ];
+        let ranked = rank_candidates(&job, pool);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].profile_id, "senior");
+    }
+
+    #[test]
+    fn score_pay_gives_nothing_for_unspecified_pay() {
+        let job = TalentMatchRequest {
+            pay_max: Some(20000.0),
+            ..Default::default()
+        };
+        let silent = features("p", json!({ "whatIWant": {} }));
+        assert_eq!(score_pay(&job, &silent).0, 0.0);
+
+        let within = features("p", json!({ "whatIWant": { "expectedSalary": "18000" } }));
+        assert_eq!(score_pay(&job, &within).0, 1.0);
+
+        let over = features("p", json!({ "whatIWant": { "expectedSalary": 25000 } }));
+        assert_eq!(score_pay(&job, &over).0, 0.75);
+    }
+}
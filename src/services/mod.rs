@@ -1,7 +1,17 @@
+pub mod api_key;
+pub mod audit;
 pub mod confirm;
+pub mod consent;
+pub mod export;
+pub mod health;
 pub mod init;
+pub mod matching;
 pub mod profile;
+pub mod rate_limit;
+pub mod saved_search;
 pub mod search;
 pub mod select;
+pub mod shortlist;
 pub mod status;
+pub mod sync;
 pub mod webhook;
@@ -1,24 +1,38 @@
+use crate::config::RedactionConfig;
+use crate::cron::saved_searches;
+use crate::db::consent::{has_valid_consent, upsert_consents};
 use crate::db::profile::{
-    delete_stale_profiles, fetch_profiles, get_candidate_by_id, store_profiles,
-    MarketInsightsParams, NewProfile, TalentSearchParams,
+    count_sync_coverage, delete_stale_profiles, fetch_profiles, get_candidate_by_id,
+    store_profiles, MarketInsightsParams, NewProfile, StoreCounts, TalentSearchParams,
 };
-use crate::models::profiles::ProfileSearchRequest;
+use crate::db::profile_unlock::{create_unlock, has_active_unlock, list_unlocks, ProfileUnlock};
+use crate::db::sync_run::{
+    complete_sync_bpp, is_sync_cancelled, record_sync_error, record_sync_page, SyncPage,
+};
+use crate::models::auth::{Caller, CallerRole};
+use crate::models::profiles::{ProfileSearchRequest, UnlockRequest};
 use crate::models::search::{
     Intent, LocationDistribution, MarketInsightsRequest as ModelMarketInsightsRequest, Pagination,
     SearchMessage, TalentSearchRequest as ModelTalentSearchRequest,
 };
-use crate::models::webhook::{Ack, AckResponse, AckStatus, WebhookPayload};
+use crate::models::webhook::{AckResponse, WebhookPayload};
+use crate::services::consent::consents_from_profiles;
+use crate::services::health::mark_sync_completed;
 use crate::state::AppState;
+use crate::utils::audit::{record_access, ClientIp};
+use crate::utils::encryption::{mask_envelopes, Keyring};
 use crate::utils::http_client::post_json;
+use crate::utils::metrics::{record_profiles_ingested, record_sync_completed};
 use crate::utils::payload_generator::build_profile_beckn_request;
+use crate::utils::redaction::{redact, RedactionView};
 
-use axum::{extract::Path, extract::State, http::StatusCode, Json};
+use axum::{extract::Path, extract::State, http::StatusCode, Extension, Json};
 use chrono::Utc;
 use deadpool_redis::redis::AsyncCommands;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
 fn hash_json(value: &Value) -> String {
@@ -71,17 +85,59 @@ fn extract_profiles_from_on_search(payload: &WebhookPayload, txn_id: &str) -> Ve
     profiles
 }
 
+/// Encrypts sensitive paths in place. Runs after hashing so unchanged
+/// profiles keep their hash despite the randomized ciphertext. Profiles
+/// that fail to encrypt are dropped rather than stored in the clear.
+fn encrypt_profiles(profiles: &mut Vec<NewProfile>, keyring: &Keyring, paths: &[String]) {
+    profiles.retain_mut(|profile| {
+        let Some(beckn) = profile.beckn_structure.as_mut() else {
+            return true;
+        };
+        match keyring.encrypt_paths(beckn, &profile.profile_id, paths) {
+            Ok(_) => true,
+            Err(e) => {
+                error!(
+                    "Encrypting profile {} failed, skipping it: {:?}",
+                    profile.profile_id, e
+                );
+                false
+            }
+        }
+    });
+}
+
+#[instrument(
+    name = "on_search",
+    skip_all,
+    fields(
+        transaction_id = %txn_id,
+        bpp_id = payload.context.bpp_id.as_deref().unwrap_or_default(),
+    )
+)]
 pub async fn handle_on_search(
-    app_state: &AppState,
+    app_state: &Arc<AppState>,
     payload: &WebhookPayload,
     txn_id: &str,
 ) -> Json<AckResponse> {
-    let profiles = extract_profiles_from_on_search(payload, txn_id);
+    match is_sync_cancelled(&app_state.db_pool, txn_id).await {
+        Ok(true) => {
+            info!("Ignoring on_search page for cancelled sync {}", txn_id);
+            return ack();
+        }
+        Ok(false) => {}
+        Err(e) => error!("is_sync_cancelled failed: {:?}", e),
+    }
+
+    let config = app_state.config.current();
+    let mut profiles = extract_profiles_from_on_search(payload, txn_id);
+    let consents = consents_from_profiles(&profiles, &config.consent.payload_path);
 
-    if let Err(e) = store_profiles(&app_state.db_pool, &profiles).await {
-        error!("store_profiles failed: {}", e);
+    if let (Some(keyring), Some(encryption)) = (&app_state.keyring, &config.encryption) {
+        encrypt_profiles(&mut profiles, keyring, &encryption.paths);
     }
 
+    let bpp_id = payload.context.bpp_id.clone().unwrap_or_default();
+
     let pagination = payload
         .message
         .get("pagination")
@@ -102,13 +158,42 @@ pub async fn handle_on_search(
         .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse::<u64>().ok()))
         .unwrap_or(0);
 
+    let stored = store_profiles(&app_state.db_pool, &profiles).await;
+    record_profiles_ingested(&bpp_id, profiles.len(), stored.is_ok());
+    let counts = match stored {
+        Ok(counts) => counts,
+        Err(e) => {
+            error!("store_profiles failed: {}", e);
+            let message = format!("store_profiles failed: {e}");
+            record_error(app_state, txn_id, &bpp_id, &message).await;
+            StoreCounts::default()
+        }
+    };
+    let sync_page = SyncPage {
+        transaction_id: txn_id,
+        bpp_id: &bpp_id,
+        page: page as i32,
+        pages_expected: (total > 0).then(|| total.div_ceil(limit) as i32),
+        items: profiles.len() as i32,
+        counts,
+    };
+    if let Err(e) = record_sync_page(&app_state.db_pool, &sync_page).await {
+        error!("record_sync_page failed: {:?}", e);
+    }
+
+    if let Err(e) = upsert_consents(&app_state.db_pool, &consents, "payload").await {
+        error!("upsert_consents failed: {}", e);
+    }
+
+    // Without a total there are no further pages to request and nothing to
+    // measure stale rows against, so the BPP is done without cleanup.
     if total == 0 {
+        complete_bpp(app_state, txn_id, &bpp_id, 0).await;
         return ack();
     }
 
-    let total_pages = (total + limit - 1) / limit;
+    let total_pages = total.div_ceil(limit);
 
-    let bpp_id = payload.context.bpp_id.clone().unwrap_or_default();
     let mut redis = match app_state.redis_pool.get().await {
         Ok(c) => c,
         Err(e) => {
@@ -117,9 +202,9 @@ pub async fn handle_on_search(
         }
     };
 
-    let base_key = format!("pagination:{}:{}:{}", txn_id, bpp_id, limit);
+    let base_key = format!("pagination:{txn_id}:{bpp_id}:{limit}");
 
-    let received_key = format!("{}:received", base_key);
+    let received_key = format!("{base_key}:received");
 
     let _: () = redis
         .hset_nx(&base_key, "total_pages", total_pages)
@@ -135,7 +220,7 @@ pub async fn handle_on_search(
     let next_page = received_pages.iter().max().copied().unwrap_or(0) + 1;
 
     if next_page <= total_pages {
-        let trigger_key = format!("{}:triggered:{}", base_key, next_page);
+        let trigger_key = format!("{base_key}:triggered:{next_page}");
 
         let triggered: bool = redis.set_nx(&trigger_key, 1).await.unwrap();
         let _: () = redis.expire(&trigger_key, 1800).await.unwrap();
@@ -158,58 +243,173 @@ pub async fn handle_on_search(
                 }),
             };
 
+            let config = app_state.config.current();
             let request_payload = build_profile_beckn_request(
-                &app_state.config,
+                &config,
                 txn_id,
                 &format!("msg-profile-{}", Uuid::new_v4()),
                 &message,
                 "search",
-                None,
-                None,
+                Some(&bpp_id),
+                payload.context.bpp_uri.as_deref(),
             );
 
-            let adapter_url = format!("{}/search", app_state.config.bap.caller_uri);
+            let adapter_url = format!("{}/search", config.bap.caller_uri);
 
             if let Err(e) = post_json(&adapter_url, request_payload).await {
                 error!("Failed to trigger next page {}: {}", next_page, e);
+                let message = format!("Failed to trigger page {next_page}: {e}");
+                record_error(app_state, txn_id, &bpp_id, &message).await;
             }
         }
     }
 
     if received_pages.len() as u64 == total_pages {
-        match delete_stale_profiles(&app_state.db_pool, &bpp_id, txn_id).await {
-            Ok(count) => info!(
-                "🧹 Stale profiles cleaned up: {} rows deleted (bpp_id={}, txn_id={})",
-                count, bpp_id, txn_id
-            ),
-            Err(e) => error!("Stale cleanup failed: {}", e),
-        };
+        let min_seen_percent = app_state.config.current().profile_sync.min_seen_percent;
+        if let Err(reason) = check_sync_coverage(app_state, &bpp_id, txn_id, min_seen_percent).await
+        {
+            warn!(
+                "Stale cleanup skipped (bpp_id={}, txn_id={}): {}",
+                bpp_id, txn_id, reason
+            );
+            let message = format!("Stale cleanup skipped: {reason}");
+            record_error(app_state, txn_id, &bpp_id, &message).await;
+            complete_bpp(app_state, txn_id, &bpp_id, 0).await;
+        } else {
+            match delete_stale_profiles(&app_state.db_pool, &bpp_id, txn_id).await {
+                Ok(count) => {
+                    record_sync_completed(&bpp_id, count);
+                    mark_sync_completed(&app_state.redis_pool).await;
+                    complete_bpp(app_state, txn_id, &bpp_id, count).await;
+                    info!(
+                        "🧹 Stale profiles soft-deleted: {} rows (bpp_id={}, txn_id={})",
+                        count, bpp_id, txn_id
+                    )
+                }
+                Err(e) => {
+                    error!("Stale cleanup failed: {}", e);
+                    let message = format!("Stale cleanup failed: {e}");
+                    record_error(app_state, txn_id, &bpp_id, &message).await;
+                    complete_bpp(app_state, txn_id, &bpp_id, 0).await;
+                }
+            };
+        }
+
+        app_state
+            .tasks
+            .spawn(saved_searches::run(app_state.clone()));
     }
 
     ack()
 }
 
+/// Refuses stale cleanup when this sync delivered less than
+/// `min_seen_percent` of the BPP's active profiles, which points at empty
+/// pages or a truncated `total` rather than profiles that were removed.
+async fn check_sync_coverage(
+    app_state: &AppState,
+    bpp_id: &str,
+    txn_id: &str,
+    min_seen_percent: f64,
+) -> Result<(), String> {
+    let (active, seen) = count_sync_coverage(&app_state.db_pool, bpp_id, txn_id)
+        .await
+        .map_err(|e| format!("coverage check failed: {e}"))?;
+
+    if coverage_too_low(active, seen, min_seen_percent) {
+        return Err(format!(
+            "sync delivered {seen} of {active} active profiles, below {min_seen_percent}%"
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `seen` of `active` profiles falls short of `min_seen_percent`.
+/// A BPP with no active profiles has nothing to protect.
+fn coverage_too_low(active: i64, seen: i64, min_seen_percent: f64) -> bool {
+    active > 0 && (seen as f64) * 100.0 < (active as f64) * min_seen_percent
+}
+
+async fn complete_bpp(app_state: &AppState, txn_id: &str, bpp_id: &str, stale_deleted: u64) {
+    if let Err(e) = complete_sync_bpp(&app_state.db_pool, txn_id, bpp_id, stale_deleted).await {
+        error!("complete_sync_bpp failed: {:?}", e);
+    }
+}
+
+/// Adds `message` to the sync run's errors; bookkeeping failures are only logged.
+async fn record_error(app_state: &AppState, txn_id: &str, bpp_id: &str, message: &str) {
+    if let Err(e) = record_sync_error(&app_state.db_pool, txn_id, bpp_id, message).await {
+        error!("record_sync_error failed: {:?}", e);
+    }
+}
+
 fn ack() -> Json<AckResponse> {
-    Json(AckResponse {
-        message: AckStatus {
-            ack: Ack { status: "ACK" },
-        },
-    })
+    Json(AckResponse::ack())
+}
+
+/// Search results carry the stored `beckn_structure`, so they get the same
+/// treatment as candidate details: envelopes are never returned, and the
+/// redaction policy applies to the profile for non-privileged callers.
+fn redact_search_item(item: &mut Value, policy: &RedactionConfig, role: CallerRole) {
+    let Some(beckn_structure) = item.get_mut("beckn_structure") else {
+        return;
+    };
+    mask_envelopes(beckn_structure);
+    if role == CallerRole::Privileged {
+        return;
+    }
+    if let Some(profile) = beckn_structure
+        .get_mut("tags")
+        .and_then(|tags| tags.get_mut("profile"))
+    {
+        redact(profile, &policy.rules, role, &policy.hash_salt);
+    }
 }
 
 pub async fn handle_search(
     State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    client_ip: ClientIp,
     Json(req): Json<ProfileSearchRequest>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let query = serde_json::to_value(&req).ok();
     let pagination = req.pagination.unwrap_or_default();
 
-    match fetch_profiles(&app_state.db_pool, pagination).await {
-        Ok(result) => Ok(Json(serde_json::json!({
-            "items": result.items,
-            "total": result.total,
-            "page": result.page,
-            "limit": result.limit
-        }))),
+    let config = app_state.config.current();
+    match fetch_profiles(
+        &app_state.db_pool,
+        pagination,
+        config.consent.search_purpose(),
+    )
+    .await
+    {
+        Ok(mut result) => {
+            for item in &mut result.items {
+                redact_search_item(item, &config.redaction, caller.role);
+            }
+            let profile_ids = result
+                .items
+                .iter()
+                .filter_map(|item| item.get("profile_id").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect();
+            record_access(
+                &app_state,
+                &caller,
+                &client_ip,
+                "search",
+                profile_ids,
+                query,
+            )
+            .await;
+
+            Ok(Json(serde_json::json!({
+                "items": result.items,
+                "total": result.total,
+                "page": result.page,
+                "limit": result.limit
+            })))
+        }
 
         Err(err) => {
             tracing::error!("fetch_profiles failed: {:?}", err);
@@ -225,37 +425,44 @@ pub async fn handle_search(
 
 pub async fn handle_talent_search(
     State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    client_ip: ClientIp,
     Json(req): Json<ModelTalentSearchRequest>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let (trade, location, experience, radius) = parse_query(req.query.as_deref());
-
-    let parsed_trade = trade.or(req.trade);
-    let parsed_location = location.or(req.location.clone());
-    let parsed_experience = experience.or(req.experience.clone());
-    let parsed_radius = radius.or(req.radius);
-
-    let params = TalentSearchParams {
-        trade: parsed_trade,
-        location: parsed_location,
-        radius: parsed_radius,
-        experience: parsed_experience,
-        page: req.page.unwrap_or(1).max(1),
-        limit: req.limit.unwrap_or(10).clamp(1, 100),
-    };
+    let config = app_state.config.current();
+    let params = build_talent_search_params(&req, config.consent.search_purpose());
+    let view = RedactionView::new(&config.redaction, caller.role);
 
     info!(
         "Searching talent: query={:?}, trade={:?}, location={:?}, experience={:?}, page={}, limit={}",
         req.query, params.trade, params.location, params.experience, params.page, params.limit
     );
 
-    match crate::db::profile::search_talent(&app_state.db_pool, params).await {
-        Ok(result) => Ok(Json(serde_json::json!({
-            "candidate_count": result.candidate_count,
-            "matched_count": result.matched_count,
-            "results": result.results,
-            "page": result.page,
-            "limit": result.limit
-        }))),
+    match crate::db::profile::search_talent(&app_state.db_pool, params, &view, &caller.id).await {
+        Ok(result) => {
+            let profile_ids = result
+                .results
+                .iter()
+                .map(|c| c.profile_id.clone())
+                .collect();
+            record_access(
+                &app_state,
+                &caller,
+                &client_ip,
+                "talent_search",
+                profile_ids,
+                serde_json::to_value(&req).ok(),
+            )
+            .await;
+
+            Ok(Json(serde_json::json!({
+                "candidate_count": result.candidate_count,
+                "matched_count": result.matched_count,
+                "results": result.results,
+                "page": result.page,
+                "limit": result.limit
+            })))
+        }
 
         Err(err) => {
             tracing::error!("search_talent failed: {:?}", err);
@@ -269,6 +476,23 @@ pub async fn handle_talent_search(
     }
 }
 
+pub fn build_talent_search_params(
+    req: &ModelTalentSearchRequest,
+    consent_purpose: Option<&str>,
+) -> TalentSearchParams {
+    let (trade, location, experience, radius) = parse_query(req.query.as_deref());
+
+    TalentSearchParams {
+        trade: trade.or(req.trade.clone()),
+        location: location.or(req.location.clone()),
+        radius: radius.or(req.radius),
+        experience: experience.or(req.experience.clone()),
+        page: req.page.unwrap_or(1).max(1),
+        limit: req.limit.unwrap_or(10).clamp(1, 100),
+        consent_purpose: consent_purpose.map(str::to_string),
+    }
+}
+
 fn parse_query(
     query: Option<&str>,
 ) -> (Option<String>, Option<String>, Option<String>, Option<i32>) {
@@ -358,7 +582,7 @@ fn parse_query(
         experience = Some("Fresher".to_string());
     } else if let Some(exp_range) = query.matches(char::is_numeric).next() {
         if query.contains("year") {
-            experience = Some(format!("{} years", exp_range));
+            experience = Some(format!("{exp_range} years"));
         }
     }
 
@@ -436,31 +660,201 @@ pub async fn handle_market_insights(
 
 pub async fn handle_candidate_details(
     State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    client_ip: ClientIp,
     Path(profile_id): Path<String>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    info!("Getting candidate details for profile_id: {}", profile_id);
+    info!(
+        "Getting candidate details for profile_id: {} (caller={})",
+        profile_id, caller.id
+    );
+
+    let unlocked = match caller.role {
+        CallerRole::Privileged => true,
+        CallerRole::Standard => has_active_unlock(&app_state.db_pool, &profile_id, &caller.id)
+            .await
+            .unwrap_or_else(|err| {
+                tracing::error!("has_active_unlock failed: {:?}", err);
+                false
+            }),
+    };
+
+    // Encrypted fields are only decrypted for callers allowed the full profile.
+    let keyring = app_state.keyring.as_deref().filter(|_| unlocked);
 
-    match get_candidate_by_id(&app_state.db_pool, &profile_id).await {
-        Ok(Some(candidate)) => Ok(Json(serde_json::json!({
-            "profile_id": candidate.profile_id,
-            "profile": candidate.profile,
-        }))),
+    let candidate =
+        match get_candidate_by_id(&app_state.db_pool, &profile_id, &caller.id, keyring).await {
+            Ok(Some(candidate)) => candidate,
 
-        Ok(None) => Err((
-            StatusCode::NOT_FOUND,
+            Ok(None) => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({
+                        "error": "Candidate not found"
+                    })),
+                ))
+            }
+
+            Err(err) => {
+                tracing::error!("get_candidate_by_id failed: {:?}", err);
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({
+                        "error": "Failed to get candidate details"
+                    })),
+                ));
+            }
+        };
+
+    let config = app_state.config.current();
+    if let Some(purpose) = config.consent.details_purpose() {
+        let consented = has_valid_consent(&app_state.db_pool, &candidate.profile_id, purpose)
+            .await
+            .unwrap_or_else(|err| {
+                tracing::error!("has_valid_consent failed: {:?}", err);
+                false
+            });
+        if !consented {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({
+                    "error": "Candidate has no valid consent for this purpose",
+                    "purpose": purpose
+                })),
+            ));
+        }
+    }
+
+    let mut profile = candidate.profile;
+    let redacted = if unlocked {
+        false
+    } else {
+        let policy = &config.redaction;
+        redact(&mut profile, &policy.rules, caller.role, &policy.hash_salt)
+    };
+
+    record_access(
+        &app_state,
+        &caller,
+        &client_ip,
+        "details",
+        vec![candidate.profile_id.clone()],
+        Some(serde_json::json!({ "redacted": redacted })),
+    )
+    .await;
+
+    Ok(Json(serde_json::json!({
+        "profile_id": candidate.profile_id,
+        "profile": profile,
+        "pipeline": candidate.pipeline,
+        "redacted": redacted,
+    })))
+}
+
+pub async fn handle_unlock_candidate(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(profile_id): Path<String>,
+    Json(req): Json<UnlockRequest>,
+) -> Result<Json<ProfileUnlock>, (StatusCode, Json<Value>)> {
+    if req.reason.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
             Json(serde_json::json!({
-                "error": "Candidate not found"
+                "error": "A reason is required to unlock contact details"
             })),
-        )),
+        ));
+    }
 
+    match get_candidate_by_id(&app_state.db_pool, &profile_id, &caller.id, None).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "error": "Candidate not found"
+                })),
+            ))
+        }
         Err(err) => {
             tracing::error!("get_candidate_by_id failed: {:?}", err);
-            Err((
+            return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({
-                    "error": "Failed to get candidate details"
+                    "error": "Failed to unlock candidate details"
                 })),
-            ))
+            ));
         }
     }
+
+    info!(
+        "🔓 Unlocking candidate details: profile_id={}, caller={}, reason={}",
+        profile_id, caller.id, req.reason
+    );
+
+    create_unlock(
+        &app_state.db_pool,
+        &profile_id,
+        &caller.id,
+        req.reason.trim(),
+        app_state.config.current().redaction.unlock_ttl_seconds,
+    )
+    .await
+    .map(Json)
+    .map_err(|err| {
+        tracing::error!("create_unlock failed: {:?}", err);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({
+                "error": "Failed to unlock candidate details"
+            })),
+        )
+    })
+}
+
+pub async fn handle_list_candidate_unlocks(
+    State(app_state): State<Arc<AppState>>,
+    Path(profile_id): Path<String>,
+) -> Result<Json<Vec<ProfileUnlock>>, (StatusCode, Json<Value>)> {
+    list_unlocks(&app_state.db_pool, &profile_id)
+        .await
+        .map(Json)
+        .map_err(|err| {
+            tracing::error!("list_unlocks failed: {:?}", err);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": "Failed to list unlocks"
+                })),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::coverage_too_low;
+
+    #[test]
+    fn coverage_with_no_active_profiles_is_never_too_low() {
+        assert!(!coverage_too_low(0, 0, 50.0));
+        assert!(!coverage_too_low(0, 10, 100.0));
+    }
+
+    #[test]
+    fn coverage_threshold_is_inclusive() {
+        assert!(!coverage_too_low(200, 100, 50.0));
+        assert!(coverage_too_low(200, 99, 50.0));
+        assert!(!coverage_too_low(3, 1, 100.0 / 3.0));
+    }
+
+    #[test]
+    fn coverage_threshold_of_zero_always_passes() {
+        assert!(!coverage_too_low(1000, 0, 0.0));
+    }
+
+    #[test]
+    fn coverage_above_active_passes() {
+        assert!(!coverage_too_low(10, 12, 100.0));
+        assert!(coverage_too_low(10, 9, 100.0));
+    }
 }
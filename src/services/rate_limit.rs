@@ -0,0 +1,26 @@
+use crate::state::AppState;
+use crate::utils::rate_limit::bucket_usage;
+
+use axum::{extract::State, http::StatusCode, Json};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::error;
+
+pub async fn handle_rate_limit_usage(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    match bucket_usage(&app_state.redis_pool).await {
+        Ok(buckets) => Ok(Json(serde_json::json!({
+            "buckets": buckets
+        }))),
+        Err(e) => {
+            error!("Failed to read rate limit usage: {:?}", e);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({
+                    "error": "Failed to read rate limit usage"
+                })),
+            ))
+        }
+    }
+}
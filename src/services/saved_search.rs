@@ -0,0 +1,144 @@
+use crate::config::SavedSearchConfig;
+use crate::db::saved_search::{
+    create_saved_search, delete_saved_search, get_saved_search, list_saved_searches,
+    update_saved_search, NewSavedSearch, SavedSearch,
+};
+use crate::models::auth::Caller;
+use crate::models::saved_search::SavedSearchRequest;
+use crate::state::AppState;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+const DEFAULT_SCHEDULE_SECONDS: i64 = 86400;
+const MIN_SCHEDULE_SECONDS: i64 = 60;
+
+fn internal_error(context: &str, err: sqlx::Error) -> (StatusCode, Json<Value>) {
+    tracing::error!("{} failed: {:?}", context, err);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({
+            "error": "Failed to process saved search"
+        })),
+    )
+}
+
+fn not_found() -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({
+            "error": "Saved search not found"
+        })),
+    )
+}
+
+/// Saved searches belong to the caller that created them; every other
+/// caller gets a 404 for them.
+fn to_new_saved_search(
+    req: SavedSearchRequest,
+    caller: &Caller,
+    config: &SavedSearchConfig,
+) -> Result<NewSavedSearch, (StatusCode, Json<Value>)> {
+    if req.name.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "name is required"
+            })),
+        ));
+    }
+    if let Some(url) = req.webhook_url.as_deref() {
+        config.check_webhook_url(url).map_err(|problem| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": problem })),
+            )
+        })?;
+    }
+
+    Ok(NewSavedSearch {
+        owner: caller.id.clone(),
+        name: req.name,
+        query: serde_json::to_value(&req.query).unwrap_or(Value::Null),
+        schedule_seconds: req
+            .schedule_seconds
+            .unwrap_or(DEFAULT_SCHEDULE_SECONDS)
+            .max(MIN_SCHEDULE_SECONDS),
+        webhook_url: req.webhook_url,
+        active: req.active.unwrap_or(true),
+    })
+}
+
+pub async fn handle_create_saved_search(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Json(req): Json<SavedSearchRequest>,
+) -> Result<(StatusCode, Json<SavedSearch>), (StatusCode, Json<Value>)> {
+    let new = to_new_saved_search(req, &caller, &app_state.config.current().saved_searches)?;
+
+    info!(
+        "Creating saved search: owner={}, name={}",
+        new.owner, new.name
+    );
+
+    create_saved_search(&app_state.db_pool, &new)
+        .await
+        .map(|s| (StatusCode::CREATED, Json(s)))
+        .map_err(|e| internal_error("create_saved_search", e))
+}
+
+pub async fn handle_list_saved_searches(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+) -> Result<Json<Vec<SavedSearch>>, (StatusCode, Json<Value>)> {
+    list_saved_searches(&app_state.db_pool, &caller.id)
+        .await
+        .map(Json)
+        .map_err(|e| internal_error("list_saved_searches", e))
+}
+
+pub async fn handle_get_saved_search(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SavedSearch>, (StatusCode, Json<Value>)> {
+    match get_saved_search(&app_state.db_pool, id, &caller.id).await {
+        Ok(Some(s)) => Ok(Json(s)),
+        Ok(None) => Err(not_found()),
+        Err(e) => Err(internal_error("get_saved_search", e)),
+    }
+}
+
+pub async fn handle_update_saved_search(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<SavedSearchRequest>,
+) -> Result<Json<SavedSearch>, (StatusCode, Json<Value>)> {
+    let new = to_new_saved_search(req, &caller, &app_state.config.current().saved_searches)?;
+
+    match update_saved_search(&app_state.db_pool, id, &new).await {
+        Ok(Some(s)) => Ok(Json(s)),
+        Ok(None) => Err(not_found()),
+        Err(e) => Err(internal_error("update_saved_search", e)),
+    }
+}
+
+pub async fn handle_delete_saved_search(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, Json<Value>)> {
+    match delete_saved_search(&app_state.db_pool, id, &caller.id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(not_found()),
+        Err(e) => Err(internal_error("delete_saved_search", e)),
+    }
+}
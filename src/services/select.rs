@@ -2,9 +2,10 @@ use crate::config::AppConfig;
 use crate::models::webhook::Context;
 
 use serde_json::Value;
-use tracing::info;
 
-use crate::utils::{payload_generator::build_beckn_payload, shared::call_provider_db};
+use crate::utils::{
+    payload_generator::build_beckn_payload, payload_log::log_payload, shared::call_provider_db,
+};
 
 pub async fn handle_select(
     context: Context,
@@ -18,7 +19,7 @@ pub async fn handle_select(
 
     let result = build_beckn_payload(config, context, &db_response);
 
-    info!("response : {:?}", result);
+    log_payload("response", &result, &config.logging.payloads);
 
     Ok(result)
 }
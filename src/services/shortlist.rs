@@ -0,0 +1,234 @@
+use crate::db::shortlist::{
+    add_shortlist_candidate, create_shortlist, delete_shortlist, get_candidate_history,
+    get_shortlist, list_shortlist_candidates, list_shortlists, remove_shortlist_candidate,
+    update_shortlist, update_shortlist_candidate, PipelineEvent, Shortlist, ShortlistCandidate,
+};
+use crate::models::auth::Caller;
+use crate::models::shortlist::{AddCandidateRequest, ShortlistRequest, UpdateCandidateRequest};
+use crate::state::AppState;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+type ApiError = (StatusCode, Json<Value>);
+
+fn error_response(status: StatusCode, message: &str) -> ApiError {
+    (
+        status,
+        Json(serde_json::json!({
+            "error": message
+        })),
+    )
+}
+
+fn db_error(context: &str, err: sqlx::Error) -> ApiError {
+    match err.as_database_error().and_then(|e| e.code()).as_deref() {
+        Some("23505") => error_response(
+            StatusCode::CONFLICT,
+            "Candidate is already on this shortlist",
+        ),
+        Some("23503") => error_response(StatusCode::NOT_FOUND, "Shortlist not found"),
+        _ => {
+            tracing::error!("{} failed: {:?}", context, err);
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to process shortlist",
+            )
+        }
+    }
+}
+
+fn validate(req: &ShortlistRequest) -> Result<(), ApiError> {
+    if req.name.trim().is_empty() {
+        return Err(error_response(StatusCode::BAD_REQUEST, "name is required"));
+    }
+    Ok(())
+}
+
+/// Shortlists belong to the caller that created them. Candidate routes
+/// check ownership first, so another caller's shortlist reads as missing.
+async fn require_owned_shortlist(
+    app_state: &AppState,
+    caller: &Caller,
+    id: Uuid,
+) -> Result<Shortlist, ApiError> {
+    match get_shortlist(&app_state.db_pool, id, &caller.id).await {
+        Ok(Some(s)) => Ok(s),
+        Ok(None) => Err(error_response(StatusCode::NOT_FOUND, "Shortlist not found")),
+        Err(e) => Err(db_error("get_shortlist", e)),
+    }
+}
+
+pub async fn handle_create_shortlist(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Json(req): Json<ShortlistRequest>,
+) -> Result<(StatusCode, Json<Shortlist>), ApiError> {
+    validate(&req)?;
+    info!("Creating shortlist: owner={}, name={}", caller.id, req.name);
+
+    create_shortlist(
+        &app_state.db_pool,
+        &caller.id,
+        &req.name,
+        req.description.as_deref(),
+    )
+    .await
+    .map(|s| (StatusCode::CREATED, Json(s)))
+    .map_err(|e| db_error("create_shortlist", e))
+}
+
+pub async fn handle_list_shortlists(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+) -> Result<Json<Vec<Shortlist>>, ApiError> {
+    list_shortlists(&app_state.db_pool, &caller.id)
+        .await
+        .map(Json)
+        .map_err(|e| db_error("list_shortlists", e))
+}
+
+pub async fn handle_get_shortlist(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let shortlist = require_owned_shortlist(&app_state, &caller, id).await?;
+
+    let candidates = list_shortlist_candidates(&app_state.db_pool, id)
+        .await
+        .map_err(|e| db_error("list_shortlist_candidates", e))?;
+
+    Ok(Json(serde_json::json!({
+        "shortlist": shortlist,
+        "candidates": candidates,
+    })))
+}
+
+pub async fn handle_update_shortlist(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ShortlistRequest>,
+) -> Result<Json<Shortlist>, ApiError> {
+    validate(&req)?;
+
+    match update_shortlist(
+        &app_state.db_pool,
+        id,
+        &caller.id,
+        &req.name,
+        req.description.as_deref(),
+    )
+    .await
+    {
+        Ok(Some(s)) => Ok(Json(s)),
+        Ok(None) => Err(error_response(StatusCode::NOT_FOUND, "Shortlist not found")),
+        Err(e) => Err(db_error("update_shortlist", e)),
+    }
+}
+
+pub async fn handle_delete_shortlist(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    match delete_shortlist(&app_state.db_pool, id, &caller.id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(error_response(StatusCode::NOT_FOUND, "Shortlist not found")),
+        Err(e) => Err(db_error("delete_shortlist", e)),
+    }
+}
+
+pub async fn handle_add_shortlist_candidate(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<AddCandidateRequest>,
+) -> Result<(StatusCode, Json<ShortlistCandidate>), ApiError> {
+    require_owned_shortlist(&app_state, &caller, id).await?;
+    info!(
+        "Adding candidate to shortlist: shortlist_id={}, profile_id={}",
+        id, req.profile_id
+    );
+
+    add_shortlist_candidate(
+        &app_state.db_pool,
+        id,
+        &req.profile_id,
+        req.stage.unwrap_or_default(),
+        req.notes.as_deref(),
+    )
+    .await
+    .map(|c| (StatusCode::CREATED, Json(c)))
+    .map_err(|e| db_error("add_shortlist_candidate", e))
+}
+
+pub async fn handle_update_shortlist_candidate(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path((id, profile_id)): Path<(Uuid, String)>,
+    Json(req): Json<UpdateCandidateRequest>,
+) -> Result<Json<ShortlistCandidate>, ApiError> {
+    if req.stage.is_none() && req.notes.is_none() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "stage or notes is required",
+        ));
+    }
+    require_owned_shortlist(&app_state, &caller, id).await?;
+
+    match update_shortlist_candidate(
+        &app_state.db_pool,
+        id,
+        &profile_id,
+        req.stage,
+        req.notes.as_deref(),
+    )
+    .await
+    {
+        Ok(Some(c)) => Ok(Json(c)),
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            "Candidate not found on shortlist",
+        )),
+        Err(e) => Err(db_error("update_shortlist_candidate", e)),
+    }
+}
+
+pub async fn handle_remove_shortlist_candidate(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path((id, profile_id)): Path<(Uuid, String)>,
+) -> Result<StatusCode, ApiError> {
+    require_owned_shortlist(&app_state, &caller, id).await?;
+
+    match remove_shortlist_candidate(&app_state.db_pool, id, &profile_id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            "Candidate not found on shortlist",
+        )),
+        Err(e) => Err(db_error("remove_shortlist_candidate", e)),
+    }
+}
+
+pub async fn handle_shortlist_candidate_history(
+    State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<Caller>,
+    Path((id, profile_id)): Path<(Uuid, String)>,
+) -> Result<Json<Vec<PipelineEvent>>, ApiError> {
+    require_owned_shortlist(&app_state, &caller, id).await?;
+
+    get_candidate_history(&app_state.db_pool, id, &profile_id)
+        .await
+        .map(Json)
+        .map_err(|e| db_error("get_candidate_history", e))
+}
@@ -0,0 +1,171 @@
+use crate::cron::fetch_profiles::{start_sync, SyncStart, SyncTarget};
+use crate::db::profile::get_bpp_uri;
+use crate::db::sync_run::{cancel_sync_run, get_sync_run, list_sync_runs};
+use crate::models::sync::{SyncRun, SyncRunStatus, SyncRunsQuery, SyncTrigger, TriggerSyncRequest};
+use crate::state::AppState;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{error, info};
+
+type ApiError = (StatusCode, Json<Value>);
+
+const DEFAULT_RUNS_LIMIT: i64 = 20;
+const MAX_RUNS_LIMIT: i64 = 200;
+
+fn error_response(status: StatusCode, message: &str) -> ApiError {
+    (
+        status,
+        Json(serde_json::json!({
+            "error": message
+        })),
+    )
+}
+
+fn db_error(context: &str, err: sqlx::Error) -> ApiError {
+    error!("{} failed: {:?}", context, err);
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Failed to read sync runs",
+    )
+}
+
+pub async fn handle_trigger_sync(
+    State(app_state): State<Arc<AppState>>,
+    body: Option<Json<TriggerSyncRequest>>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    let bpp_id = req
+        .bpp_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+
+    let target = match bpp_id {
+        Some(bpp_id) => {
+            let bpp_uri = match req.bpp_uri.filter(|uri| !uri.trim().is_empty()) {
+                Some(uri) => Some(uri),
+                None => get_bpp_uri(&app_state.db_pool, bpp_id).await.map_err(|e| {
+                    error!("get_bpp_uri failed: {:?}", e);
+                    error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to look up BPP")
+                })?,
+            };
+            let Some(bpp_uri) = bpp_uri else {
+                return Err(error_response(
+                    StatusCode::BAD_REQUEST,
+                    "Unknown bpp_id; pass bpp_uri to sync a BPP with no stored profiles",
+                ));
+            };
+            Some(SyncTarget {
+                bpp_id: bpp_id.to_string(),
+                bpp_uri,
+            })
+        }
+        None => None,
+    };
+
+    // A full sync overlaps every run; a per-BPP sync only overlaps full
+    // runs and runs for the same BPP.
+    let started = start_sync(&app_state, SyncTrigger::Admin, target.as_ref())
+        .await
+        .map_err(|e| {
+            error!("Admin sync failed to start: {:?}", e);
+            if e.downcast_ref::<sqlx::Error>().is_some() {
+                error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to register sync run",
+                )
+            } else {
+                error_response(
+                    StatusCode::BAD_GATEWAY,
+                    "Failed to send search to BAP adapter",
+                )
+            }
+        })?;
+    let txn_id = match started {
+        SyncStart::Started(txn_id) => txn_id,
+        SyncStart::AlreadyRunning(running) => {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({
+                    "error": "A sync covering this BPP is already running",
+                    "transaction_id": running
+                })),
+            ));
+        }
+    };
+    info!("Admin triggered sync {} (bpp: {:?})", txn_id, bpp_id);
+
+    let run = get_sync_run(&app_state.db_pool, &txn_id)
+        .await
+        .map_err(|e| db_error("get_sync_run", e))?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "transaction_id": txn_id,
+            "run": run
+        })),
+    ))
+}
+
+pub async fn handle_list_sync_runs(
+    State(app_state): State<Arc<AppState>>,
+    Query(query): Query<SyncRunsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_RUNS_LIMIT)
+        .clamp(1, MAX_RUNS_LIMIT);
+    let runs = list_sync_runs(&app_state.db_pool, limit)
+        .await
+        .map_err(|e| db_error("list_sync_runs", e))?;
+
+    Ok(Json(serde_json::json!({ "runs": runs })))
+}
+
+pub async fn handle_get_sync_run(
+    State(app_state): State<Arc<AppState>>,
+    Path(txn_id): Path<String>,
+) -> Result<Json<SyncRun>, ApiError> {
+    get_sync_run(&app_state.db_pool, &txn_id)
+        .await
+        .map_err(|e| db_error("get_sync_run", e))?
+        .map(Json)
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, "Sync run not found"))
+}
+
+pub async fn handle_cancel_sync_run(
+    State(app_state): State<Arc<AppState>>,
+    Path(txn_id): Path<String>,
+) -> Result<Json<SyncRun>, ApiError> {
+    let run = get_sync_run(&app_state.db_pool, &txn_id)
+        .await
+        .map_err(|e| db_error("get_sync_run", e))?
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, "Sync run not found"))?;
+    if run.status != SyncRunStatus::Running {
+        return Err(error_response(
+            StatusCode::CONFLICT,
+            "Sync run is no longer running",
+        ));
+    }
+
+    let cancelled = cancel_sync_run(&app_state.db_pool, &txn_id)
+        .await
+        .map_err(|e| db_error("cancel_sync_run", e))?;
+    if !cancelled {
+        // Pages arrived for a transaction this service has no run row for.
+        return Err(error_response(StatusCode::NOT_FOUND, "Sync run not found"));
+    }
+    info!("Admin cancelled sync {}", txn_id);
+
+    get_sync_run(&app_state.db_pool, &txn_id)
+        .await
+        .map_err(|e| db_error("get_sync_run", e))?
+        .map(Json)
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, "Sync run not found"))
+}
@@ -1,21 +1,23 @@
-use crate::config::AppConfig;
+use crate::config::{AppConfig, RateLimit};
 use crate::models::webhook::Context;
-use crate::models::webhook::{Ack, AckResponse, AckStatus, WebhookPayload};
+use crate::models::webhook::{AckResponse, WebhookPayload};
 use crate::services::{
     confirm::handle_confirm, init::handle_init, profile::handle_on_search, search::handle_search,
     select::handle_select, status::handle_status,
 };
 use crate::state::AppState;
 use crate::utils::mock_responses::load_mock_response;
+use crate::utils::rate_limit::enforce;
 use crate::workers::processor::spawn_processing_task;
 use axum::{
     extract::{Path, State},
-    response::IntoResponse,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde_json::Value;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
 pub async fn generate_response(
@@ -65,11 +67,65 @@ pub async fn generate_response(
     }
 }
 
+/// NACKs the request when `bap_id` has exhausted its token bucket.
+async fn check_bap_rate_limit(app_state: &AppState, bap_id: &str) -> Option<Response> {
+    let limit = app_state.config.current().rate_limits.for_bap(bap_id)?;
+    check_rate_limit(app_state, "bap", bap_id, &limit).await
+}
+
+/// NACKs a profile sync page when `bpp_id` has exhausted its token bucket.
+/// Kept apart from the bap limits so a sync sharing its bap_id with search
+/// traffic is not throttled by it.
+async fn check_bpp_rate_limit(app_state: &AppState, bpp_id: &str) -> Option<Response> {
+    let limit = app_state.config.current().rate_limits.for_bpp(bpp_id)?;
+    check_rate_limit(app_state, "bpp", bpp_id, &limit).await
+}
+
+async fn check_rate_limit(
+    app_state: &AppState,
+    kind: &str,
+    id: &str,
+    limit: &RateLimit,
+) -> Option<Response> {
+    let decision = enforce(&app_state.redis_pool, &format!("{kind}:{id}"), limit).await?;
+    warn!(
+        target: "webhook",
+        "⛔ Rate limit exceeded for {}_id={}, retry in {}s",
+        kind, id, decision.retry_after_secs
+    );
+    Some(rate_limited_nack(id, limit, decision.retry_after_secs))
+}
+
+/// 429 NACK with a Retry-After header, in the ONDC ack envelope.
+fn rate_limited_nack(id: &str, limit: &RateLimit, retry_after_secs: u64) -> Response {
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after_secs.to_string())],
+        Json(AckResponse::nack(
+            "RATE_LIMITED",
+            format!(
+                "Rate limit of {} requests per {}s exceeded for {}",
+                limit.requests, limit.period_seconds, id
+            ),
+        )),
+    )
+        .into_response()
+}
+
+#[instrument(
+    name = "webhook",
+    skip_all,
+    fields(
+        transaction_id = %payload.context.transaction_id,
+        message_id = %payload.context.message_id,
+        action = %action,
+    )
+)]
 pub async fn webhook_handler(
     Path(action): Path<String>,
     State(app_state): State<Arc<AppState>>,
     Json(payload): Json<WebhookPayload>,
-) -> impl IntoResponse {
+) -> Response {
     info!(
         target: "webhook",
         "🟢 [ Adapter → BPP] Request received | txn_id: {}, msg_id: {}, action: {}, timestamp: {}",
@@ -80,53 +136,87 @@ pub async fn webhook_handler(
     );
 
     debug!(target: "webhook", "🔎 Message payload: {:?}", payload.message);
+    if let Some(nack) = check_bap_rate_limit(&app_state, &payload.context.bap_id).await {
+        return nack;
+    }
+
     if action.starts_with("on_") {
         info!(
             "Skipping processing since action starts with 'on_': {:?}",
             action
         );
-        let ack = AckResponse {
-            message: AckStatus {
-                ack: Ack { status: "ACK" },
-            },
-        };
-        return Json(ack);
+        return Json(AckResponse::ack()).into_response();
     }
 
     spawn_processing_task(
         payload.context,
         payload.message,
         action,
-        app_state.config.clone(),
+        app_state.config.current(),
+        &app_state.tasks,
     );
 
-    let ack = AckResponse {
-        message: AckStatus {
-            ack: Ack { status: "ACK" },
-        },
-    };
-
-    Json(ack)
+    Json(AckResponse::ack()).into_response()
 }
 
+#[instrument(
+    name = "webhook",
+    skip_all,
+    fields(
+        transaction_id = %payload.context.transaction_id,
+        message_id = %payload.context.message_id,
+        action = %action,
+    )
+)]
 pub async fn webhook_handler_profiles(
     Path(action): Path<String>,
     State(app_state): State<Arc<AppState>>,
     Json(payload): Json<WebhookPayload>,
-) -> impl IntoResponse {
+) -> Response {
     let txn_id = payload.context.transaction_id.clone();
 
     info!("webhook called: action = {}, txn_id = {}", action, txn_id);
 
+    let bpp_id = payload.context.bpp_id.as_deref().unwrap_or_default();
+    if let Some(nack) = check_bpp_rate_limit(&app_state, bpp_id).await {
+        return nack;
+    }
+
     match action.as_str() {
-        "on_search" => handle_on_search(&app_state, &payload, &txn_id).await,
+        "on_search" => handle_on_search(&app_state, &payload, &txn_id)
+            .await
+            .into_response(),
         _ => {
             info!("Unsupported action for profiles: {}", action);
-            Json(AckResponse {
-                message: AckStatus {
-                    ack: Ack { status: "ACK" },
-                },
-            })
+            Json(AckResponse::ack()).into_response()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rate_limited_nack_carries_retry_after() {
+        let limit = RateLimit {
+            requests: 50,
+            period_seconds: 60,
+        };
+        let response = rate_limited_nack("busy.bap", &limit, 2);
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["message"]["ack"]["status"], "NACK");
+        assert_eq!(body["error"]["code"], "RATE_LIMITED");
+        assert_eq!(
+            body["error"]["message"],
+            "Rate limit of 50 requests per 60s exceeded for busy.bap"
+        );
+    }
+}
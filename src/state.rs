@@ -1,11 +1,42 @@
 use crate::config::AppConfig;
+use crate::utils::encryption::Keyring;
+use crate::utils::jwt::JwtVerifier;
+use arc_swap::ArcSwap;
 use deadpool_redis::Pool;
+use metrics_exporter_prometheus::PrometheusHandle;
 use sqlx::PgPool;
 use std::sync::Arc;
+use tokio_util::task::TaskTracker;
+
+/// Live configuration. Readers take a snapshot with `current()` and keep
+/// using it for the rest of their work; a reload swaps in a new one
+/// without blocking them.
+#[derive(Clone)]
+pub struct SharedConfig(Arc<ArcSwap<AppConfig>>);
+
+impl SharedConfig {
+    pub fn new(config: AppConfig) -> Self {
+        Self(Arc::new(ArcSwap::from_pointee(config)))
+    }
+
+    pub fn current(&self) -> Arc<AppConfig> {
+        self.0.load_full()
+    }
+
+    pub fn replace(&self, config: AppConfig) -> Arc<AppConfig> {
+        self.0.swap(Arc::new(config))
+    }
+}
 
 #[derive(Clone)]
 pub struct AppState {
-    pub config: Arc<AppConfig>,
+    pub config: SharedConfig,
     pub redis_pool: Pool,
     pub db_pool: PgPool,
+    pub jwt: Option<Arc<JwtVerifier>>,
+    pub keyring: Option<Arc<Keyring>>,
+    /// Background work (webhook processing, cron runs) that shutdown waits for.
+    pub tasks: TaskTracker,
+    /// Set when `metrics.enabled`; renders the `/metrics` scrape.
+    pub metrics: Option<PrometheusHandle>,
 }
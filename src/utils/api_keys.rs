@@ -0,0 +1,96 @@
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+const KEY_MARKER: &str = "bpp";
+const PREFIX_LEN: usize = 8;
+const SECRET_LEN: usize = 40;
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// Returns a new `bpp_<prefix>_<secret>` key together with its prefix. Only
+/// the prefix and the hash of the full key are ever stored.
+pub fn generate_api_key() -> (String, String) {
+    let prefix = random_string(PREFIX_LEN);
+    let secret = random_string(SECRET_LEN);
+    (format!("{KEY_MARKER}_{prefix}_{secret}"), prefix)
+}
+
+pub fn key_prefix(key: &str) -> Option<&str> {
+    let mut parts = key.splitn(3, '_');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(KEY_MARKER), Some(prefix), Some(secret))
+            if prefix.len() == PREFIX_LEN && !secret.is_empty() =>
+        {
+            Some(prefix)
+        }
+        _ => None,
+    }
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Compares two secrets without leaking where they differ. Both sides are
+/// hashed first so the comparison length does not depend on the input.
+pub fn secrets_match(provided: &str, expected: &str) -> bool {
+    let a = Sha256::digest(provided.as_bytes());
+    let b = Sha256::digest(expected.as_bytes());
+    a.ct_eq(&b).into()
+}
+
+pub fn verify_api_key(key: &str, stored_hash: &str) -> bool {
+    hash_api_key(key)
+        .as_bytes()
+        .ct_eq(stored_hash.as_bytes())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_keys_yield_their_prefix() {
+        let (key, prefix) = generate_api_key();
+        assert_eq!(key_prefix(&key), Some(prefix.as_str()));
+        assert_eq!(key.len(), KEY_MARKER.len() + PREFIX_LEN + SECRET_LEN + 2);
+    }
+
+    #[test]
+    fn key_prefix_rejects_malformed_keys() {
+        assert_eq!(key_prefix("bpp_abcdefgh_secret"), Some("abcdefgh"));
+        // Secrets may contain the separator.
+        assert_eq!(key_prefix("bpp_abcdefgh_sec_ret"), Some("abcdefgh"));
+        assert_eq!(key_prefix("bpp_abcdefgh_"), None);
+        assert_eq!(key_prefix("bpp_abcdefgh"), None);
+        assert_eq!(key_prefix("bpp_abc_secret"), None);
+        assert_eq!(key_prefix("xyz_abcdefgh_secret"), None);
+        assert_eq!(key_prefix(""), None);
+    }
+
+    #[test]
+    fn secrets_match_compares_whole_values() {
+        assert!(secrets_match("s3cret", "s3cret"));
+        assert!(!secrets_match("s3cret", "s3cre"));
+        assert!(!secrets_match("s3cret", "S3cret"));
+        assert!(!secrets_match("", "s3cret"));
+        assert!(secrets_match("", ""));
+    }
+
+    #[test]
+    fn verify_api_key_checks_the_stored_hash() {
+        let (key, _) = generate_api_key();
+        let hash = hash_api_key(&key);
+        assert!(verify_api_key(&key, &hash));
+        assert!(!verify_api_key(&format!("{key}x"), &hash));
+        assert!(!verify_api_key(&key, &hash[1..]));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::db::access_audit::{insert_access_record, NewAccessRecord};
+use crate::models::auth::{Caller, CallerRole};
+use crate::state::AppState;
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+use serde_json::Value;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::error;
+
+/// Best-effort client address for the access log. Forwarding headers are
+/// only honoured when `audit.trust_forwarded_for` is set.
+#[derive(Debug, Clone)]
+pub struct ClientIp(pub Option<String>);
+
+impl FromRequestParts<Arc<AppState>> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if state.config.current().audit.trust_forwarded_for {
+            let forwarded = parts
+                .headers
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .or_else(|| parts.headers.get("x-real-ip").and_then(|v| v.to_str().ok()))
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+
+            if let Some(ip) = forwarded {
+                return Ok(ClientIp(Some(ip.to_string())));
+            }
+        }
+
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string());
+
+        Ok(ClientIp(peer))
+    }
+}
+
+/// Appends an access record for candidate data returned to `caller`.
+/// Failures are logged rather than surfaced so the audit table being
+/// unavailable does not take the API down with it.
+pub async fn record_access(
+    app_state: &AppState,
+    caller: &Caller,
+    client_ip: &ClientIp,
+    action: &str,
+    profile_ids: Vec<String>,
+    query: Option<Value>,
+) {
+    let record = NewAccessRecord {
+        caller_id: &caller.id,
+        auth_method: caller.auth_method.as_str(),
+        caller_role: caller.role.as_str(),
+        action,
+        profile_ids,
+        query,
+        client_ip: client_ip.0.clone(),
+    };
+
+    if let Err(e) = insert_access_record(&app_state.db_pool, &record).await {
+        error!(
+            "Failed to write access record (caller={}, action={}): {:?}",
+            caller.id, action, e
+        );
+    }
+}
+
+/// Appends an access record for candidate data the service sends out on
+/// its own, e.g. saved search alerts, attributed to `owner`.
+pub async fn record_system_access(
+    app_state: &AppState,
+    owner: &str,
+    action: &str,
+    profile_ids: Vec<String>,
+    query: Option<Value>,
+) {
+    let record = NewAccessRecord {
+        caller_id: owner,
+        auth_method: "system",
+        caller_role: CallerRole::Standard.as_str(),
+        action,
+        profile_ids,
+        query,
+        client_ip: None,
+    };
+
+    if let Err(e) = insert_access_record(&app_state.db_pool, &record).await {
+        error!(
+            "Failed to write access record (owner={}, action={}): {:?}",
+            owner, action, e
+        );
+    }
+}
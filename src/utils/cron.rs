@@ -1,17 +1,145 @@
-pub fn build_cron_expr(seconds: u64) -> (String, String) {
-    let desc = if seconds < 60 {
-        format!("every {} seconds", seconds)
-    } else if seconds % 60 == 0 {
-        format!("every {} minutes", seconds / 60)
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
+use uuid::Uuid;
+
+/// How a periodic job is triggered. Intervals that divide a minute, hour or
+/// day evenly use a cron expression so runs line up with the clock; any
+/// other interval (90s, 45m, 5h) repeats on a fixed timer rather than being
+/// rounded to a cron step that fires at a different rate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    Cron(String),
+    Every(Duration),
+}
+
+impl Schedule {
+    pub fn job<T>(&self, run: T) -> Result<Job, JobSchedulerError>
+    where
+        T: FnMut(Uuid, JobScheduler) -> Pin<Box<dyn Future<Output = ()> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        match self {
+            Schedule::Cron(expr) => Job::new_async(expr.as_str(), run),
+            Schedule::Every(interval) => Job::new_repeated_async(*interval, run),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Cron(expr) => write!(f, "{expr}"),
+            Schedule::Every(interval) => write!(f, "fixed interval of {}s", interval.as_secs()),
+        }
+    }
+}
+
+pub fn describe_interval(seconds: u64) -> String {
+    let parts: Vec<String> = [
+        (seconds / 3600, "hour"),
+        (seconds % 3600 / 60, "minute"),
+        (seconds % 60, "second"),
+    ]
+    .into_iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, unit)| format!("{n} {unit}{}", if n == 1 { "" } else { "s" }))
+    .collect();
+
+    if parts.is_empty() {
+        "every 0 seconds".to_string()
     } else {
-        format!("every {} minutes {} seconds", seconds / 60, seconds % 60)
-    };
+        format!("every {}", parts.join(" "))
+    }
+}
+
+pub fn build_schedule(seconds: u64) -> (String, Schedule) {
+    let seconds = seconds.max(1);
+    let (minutes, hours) = (seconds / 60, seconds / 3600);
 
-    let expr = if seconds < 60 {
-        format!("*/{} * * * * *", seconds)
+    let schedule = if seconds < 60 && 60 % seconds == 0 {
+        Schedule::Cron(format!("*/{seconds} * * * * *"))
+    } else if seconds % 60 == 0 && minutes < 60 && 60 % minutes == 0 {
+        Schedule::Cron(format!("0 */{minutes} * * * *"))
+    } else if seconds % 3600 == 0 && hours < 24 && 24 % hours == 0 {
+        Schedule::Cron(format!("0 0 */{hours} * * *"))
+    } else if seconds == 86_400 {
+        Schedule::Cron("0 0 0 * * *".to_string())
     } else {
-        format!("0 */{} * * * *", seconds / 60)
+        Schedule::Every(Duration::from_secs(seconds))
     };
 
-    (desc, expr)
+    (describe_interval(seconds), schedule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cron(expr: &str) -> Schedule {
+        Schedule::Cron(expr.to_string())
+    }
+
+    fn every(seconds: u64) -> Schedule {
+        Schedule::Every(Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn ninety_seconds_runs_on_a_fixed_timer() {
+        // Used to become "0 */1 * * * *", firing every minute.
+        assert_eq!(
+            build_schedule(90),
+            ("every 1 minute 30 seconds".into(), every(90))
+        );
+    }
+
+    #[test]
+    fn sub_minute_intervals() {
+        assert_eq!(build_schedule(15).1, cron("*/15 * * * * *"));
+        assert_eq!(build_schedule(1).1, cron("*/1 * * * * *"));
+        assert_eq!(build_schedule(0).1, cron("*/1 * * * * *"));
+        assert_eq!(build_schedule(45).1, every(45));
+    }
+
+    #[test]
+    fn minute_intervals() {
+        assert_eq!(build_schedule(60).1, cron("0 */1 * * * *"));
+        assert_eq!(build_schedule(300).1, cron("0 */5 * * * *"));
+        assert_eq!(build_schedule(1800).1, cron("0 */30 * * * *"));
+        // 45 minutes does not divide the hour.
+        assert_eq!(build_schedule(2700).1, every(2700));
+        assert_eq!(build_schedule(61).1, every(61));
+    }
+
+    #[test]
+    fn hour_and_longer_intervals() {
+        assert_eq!(build_schedule(3600).1, cron("0 0 */1 * * *"));
+        assert_eq!(build_schedule(6 * 3600).1, cron("0 0 */6 * * *"));
+        assert_eq!(build_schedule(86_400).1, cron("0 0 0 * * *"));
+        assert_eq!(build_schedule(5 * 3600).1, every(5 * 3600));
+        assert_eq!(build_schedule(5400).1, every(5400));
+        assert_eq!(build_schedule(2 * 86_400).1, every(2 * 86_400));
+    }
+
+    #[test]
+    fn describes_intervals() {
+        assert_eq!(describe_interval(3661), "every 1 hour 1 minute 1 second");
+        assert_eq!(describe_interval(7200), "every 2 hours");
+        assert_eq!(describe_interval(0), "every 0 seconds");
+    }
+
+    #[test]
+    fn cron_expressions_are_accepted_by_the_scheduler() {
+        for seconds in [15, 60, 300, 3600, 86_400] {
+            let schedule = build_schedule(seconds).1;
+            assert!(
+                schedule.job(|_, _| Box::pin(async {})).is_ok(),
+                "{schedule} rejected"
+            );
+        }
+    }
 }
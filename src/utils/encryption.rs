@@ -0,0 +1,462 @@
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Nonce,
+};
+use anyhow::{anyhow, bail, Context};
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+const ENVELOPE_TAG: &str = "$enc";
+const ENVELOPE_ALG: &str = "A256GCM";
+const NONCE_LEN: usize = 12;
+/// Envelope format version. The field ciphertext is bound to its profile id
+/// and field path; envelopes of any other version are rejected.
+const ENVELOPE_VERSION: u64 = 1;
+const MASK: &str = "****";
+
+#[derive(Deserialize)]
+struct KeyringFile {
+    active_kid: String,
+    /// kid -> base64-encoded 256-bit key-encryption key.
+    keys: HashMap<String, String>,
+}
+
+/// Key-encryption keys by id. Each encrypted field carries its own data
+/// key, wrapped by the active KEK, so rotating only rewraps data keys.
+pub struct Keyring {
+    active_kid: String,
+    keys: HashMap<String, Aes256Gcm>,
+}
+
+impl Keyring {
+    /// Loads a JSON keyring file: `{"active_kid": "...", "keys": {"kid": "base64"}}`.
+    /// This stands in for a KMS; only the file needs to change to swap it out.
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading keyring file {path}"))?;
+        let file: KeyringFile =
+            serde_json::from_str(&raw).with_context(|| format!("parsing keyring file {path}"))?;
+
+        let mut keys = HashMap::new();
+        for (kid, encoded) in file.keys {
+            let bytes = B64
+                .decode(encoded.trim())
+                .with_context(|| format!("key {kid} is not valid base64"))?;
+            let cipher = Aes256Gcm::new_from_slice(&bytes)
+                .map_err(|_| anyhow!("key {kid} must be 32 bytes, got {}", bytes.len()))?;
+            keys.insert(kid, cipher);
+        }
+
+        if !keys.contains_key(&file.active_kid) {
+            bail!("active_kid {} is not in the keyring", file.active_kid);
+        }
+
+        Ok(Self {
+            active_kid: file.active_kid,
+            keys,
+        })
+    }
+
+    pub fn active_kid(&self) -> &str {
+        &self.active_kid
+    }
+
+    fn kek(&self, kid: &str) -> anyhow::Result<&Aes256Gcm> {
+        self.keys
+            .get(kid)
+            .ok_or_else(|| anyhow!("unknown key id {kid}"))
+    }
+
+    fn wrap_dek(&self, dek: &[u8]) -> anyhow::Result<String> {
+        let kek = self.kek(&self.active_kid)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let wrapped = kek
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: dek,
+                    aad: self.active_kid.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("failed to wrap data key"))?;
+        Ok(B64.encode([nonce.as_slice(), &wrapped].concat()))
+    }
+
+    fn unwrap_dek(&self, kid: &str, wrapped: &str) -> anyhow::Result<Vec<u8>> {
+        let raw = B64.decode(wrapped)?;
+        if raw.len() <= NONCE_LEN {
+            bail!("wrapped data key is truncated");
+        }
+        let (nonce, ct) = raw.split_at(NONCE_LEN);
+        self.kek(kid)?
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ct,
+                    aad: kid.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("failed to unwrap data key with {kid}"))
+    }
+
+    /// Encrypts one field of `profile_id` found at `path`, so the envelope
+    /// cannot be moved to another row or field without failing to decrypt.
+    pub fn encrypt_value(
+        &self,
+        plaintext: &Value,
+        profile_id: &str,
+        path: &str,
+    ) -> anyhow::Result<Value> {
+        let dek = Aes256Gcm::generate_key(OsRng);
+        let cipher = Aes256Gcm::new(&dek);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ct = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: serde_json::to_vec(plaintext)?.as_slice(),
+                    aad: field_aad(profile_id, path).as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("failed to encrypt field"))?;
+
+        let mut envelope = Map::new();
+        envelope.insert(ENVELOPE_TAG.into(), ENVELOPE_ALG.into());
+        envelope.insert("v".into(), ENVELOPE_VERSION.into());
+        envelope.insert("kid".into(), self.active_kid.clone().into());
+        envelope.insert("dek".into(), self.wrap_dek(dek.as_slice())?.into());
+        envelope.insert("nonce".into(), B64.encode(nonce).into());
+        envelope.insert("ct".into(), B64.encode(ct).into());
+        Ok(Value::Object(envelope))
+    }
+
+    pub fn decrypt_value(
+        &self,
+        envelope: &Value,
+        profile_id: &str,
+        path: &str,
+    ) -> anyhow::Result<Value> {
+        let field = |name: &str| {
+            envelope
+                .get(name)
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("envelope is missing {name}"))
+        };
+
+        check_version(envelope)?;
+        let dek = self.unwrap_dek(field("kid")?, field("dek")?)?;
+        let cipher =
+            Aes256Gcm::new_from_slice(&dek).map_err(|_| anyhow!("data key has wrong length"))?;
+        let nonce = B64.decode(field("nonce")?)?;
+        if nonce.len() != NONCE_LEN {
+            bail!("envelope nonce has wrong length");
+        }
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: B64.decode(field("ct")?)?.as_slice(),
+                    aad: field_aad(profile_id, path).as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("failed to decrypt field at {path}"))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    /// Re-wraps the envelope's data key under the active KEK. The field
+    /// ciphertext itself is untouched.
+    fn rewrap(&self, envelope: &mut Value) -> anyhow::Result<bool> {
+        check_version(envelope)?;
+        let Some(kid) = envelope.get("kid").and_then(Value::as_str) else {
+            bail!("envelope is missing kid");
+        };
+        if kid == self.active_kid {
+            return Ok(false);
+        }
+        let wrapped = envelope
+            .get("dek")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("envelope is missing dek"))?;
+
+        let dek = self.unwrap_dek(kid, wrapped)?;
+        let rewrapped = self.wrap_dek(&dek)?;
+        envelope["kid"] = self.active_kid.clone().into();
+        envelope["dek"] = rewrapped.into();
+        Ok(true)
+    }
+
+    /// Encrypts the plaintext values at each dot path (`*` matches every
+    /// array item or key) of a profile's `beckn_structure`. Returns the
+    /// number of fields encrypted.
+    pub fn encrypt_paths(
+        &self,
+        value: &mut Value,
+        profile_id: &str,
+        paths: &[String],
+    ) -> anyhow::Result<usize> {
+        let mut count = 0;
+        for path in paths {
+            let segments: Vec<&str> = path.split('.').filter(|s| !s.is_empty()).collect();
+            visit_path(value, &segments, &mut Vec::new(), &mut |target, at| {
+                if target.is_null() || is_envelope(target) {
+                    return Ok(());
+                }
+                *target = self.encrypt_value(target, profile_id, at)?;
+                count += 1;
+                Ok(())
+            })?;
+        }
+        Ok(count)
+    }
+
+    /// Replaces every envelope in `value` with its plaintext. `base` is the
+    /// dot path of `value` within the profile's `beckn_structure`.
+    pub fn decrypt_all(
+        &self,
+        value: &mut Value,
+        profile_id: &str,
+        base: &str,
+    ) -> anyhow::Result<usize> {
+        let mut count = 0;
+        visit_envelopes(value, &mut base_path(base), &mut |envelope, at| {
+            *envelope = self.decrypt_value(envelope, profile_id, at)?;
+            count += 1;
+            Ok(())
+        })?;
+        Ok(count)
+    }
+
+    /// Moves every envelope in a profile's `beckn_structure` onto the
+    /// active key.
+    pub fn rewrap_all(&self, value: &mut Value) -> anyhow::Result<usize> {
+        let mut count = 0;
+        visit_envelopes(value, &mut Vec::new(), &mut |envelope, _| {
+            if self.rewrap(envelope)? {
+                count += 1;
+            }
+            Ok(())
+        })?;
+        Ok(count)
+    }
+}
+
+pub fn is_envelope(value: &Value) -> bool {
+    value
+        .get(ENVELOPE_TAG)
+        .and_then(Value::as_str)
+        .is_some_and(|alg| alg == ENVELOPE_ALG)
+}
+
+fn check_version(envelope: &Value) -> anyhow::Result<()> {
+    match envelope.get("v").and_then(Value::as_u64) {
+        Some(ENVELOPE_VERSION) => Ok(()),
+        Some(v) => bail!("unsupported envelope version {v}"),
+        None => bail!("envelope is missing its version"),
+    }
+}
+
+/// Length-prefixed so no profile id and path pair can collide with another.
+fn field_aad(profile_id: &str, path: &str) -> String {
+    format!("{}:{}{}", profile_id.len(), profile_id, path)
+}
+
+fn base_path(base: &str) -> Vec<String> {
+    base.split('.')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Replaces envelopes with a mask for callers not allowed to see them.
+pub fn mask_envelopes(value: &mut Value) -> usize {
+    let mut count = 0;
+    let _ = visit_envelopes(value, &mut Vec::new(), &mut |envelope, _| {
+        *envelope = Value::String(MASK.to_string());
+        count += 1;
+        Ok(())
+    });
+    count
+}
+
+/// The dot path of the value being visited, with array items by index.
+type Visitor<'a> = dyn FnMut(&mut Value, &str) -> anyhow::Result<()> + 'a;
+
+fn visit_path(
+    value: &mut Value,
+    segments: &[&str],
+    at: &mut Vec<String>,
+    f: &mut Visitor,
+) -> anyhow::Result<()> {
+    let Some((head, rest)) = segments.split_first() else {
+        return f(value, &at.join("."));
+    };
+
+    match (value, *head) {
+        (Value::Array(items), "*") => items
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(i, v)| visit_child(at, i.to_string(), |at| visit_path(v, rest, at, f))),
+        (Value::Object(map), "*") => map
+            .iter_mut()
+            .try_for_each(|(k, v)| visit_child(at, k.clone(), |at| visit_path(v, rest, at, f))),
+        (Value::Object(map), key) => match map.get_mut(key) {
+            Some(v) => visit_child(at, key.to_string(), |at| visit_path(v, rest, at, f)),
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+fn visit_envelopes(value: &mut Value, at: &mut Vec<String>, f: &mut Visitor) -> anyhow::Result<()> {
+    if is_envelope(value) {
+        return f(value, &at.join("."));
+    }
+    match value {
+        Value::Array(items) => items
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(i, v)| visit_child(at, i.to_string(), |at| visit_envelopes(v, at, f))),
+        Value::Object(map) => map
+            .iter_mut()
+            .try_for_each(|(k, v)| visit_child(at, k.clone(), |at| visit_envelopes(v, at, f))),
+        _ => Ok(()),
+    }
+}
+
+fn visit_child(
+    at: &mut Vec<String>,
+    segment: String,
+    visit: impl FnOnce(&mut Vec<String>) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    at.push(segment);
+    let result = visit(at);
+    at.pop();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn keyring(active_kid: &str, kids: &[&str]) -> Keyring {
+        let keys: HashMap<&str, String> = kids
+            .iter()
+            .map(|kid| (*kid, B64.encode([kid.as_bytes()[0]; 32])))
+            .collect();
+        let path = std::env::temp_dir().join(format!("keyring-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            json!({ "active_kid": active_kid, "keys": keys }).to_string(),
+        )
+        .unwrap();
+        let keyring = Keyring::load(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        keyring.unwrap()
+    }
+
+    fn profile() -> Value {
+        json!({
+            "tags": { "profile": {
+                "whoIAm": { "email": "asha@example.com", "phone": "9876543210" },
+                "contacts": [{ "phone": "1" }, { "phone": "2" }]
+            } }
+        })
+    }
+
+    fn paths() -> Vec<String> {
+        vec![
+            "tags.profile.whoIAm.email".into(),
+            "tags.profile.contacts.*.phone".into(),
+        ]
+    }
+
+    #[test]
+    fn round_trip_restores_plaintext() {
+        let keys = keyring("k1", &["k1"]);
+        let mut value = profile();
+        assert_eq!(keys.encrypt_paths(&mut value, "p1", &paths()).unwrap(), 3);
+        assert!(is_envelope(&value["tags"]["profile"]["whoIAm"]["email"]));
+        assert_eq!(value["tags"]["profile"]["whoIAm"]["phone"], "9876543210");
+
+        // Already encrypted fields are left alone.
+        assert_eq!(keys.encrypt_paths(&mut value, "p1", &paths()).unwrap(), 0);
+
+        let mut sub = value["tags"]["profile"].clone();
+        assert_eq!(keys.decrypt_all(&mut sub, "p1", "tags.profile").unwrap(), 3);
+        assert_eq!(sub, profile()["tags"]["profile"]);
+    }
+
+    #[test]
+    fn envelopes_are_bound_to_profile_and_path() {
+        let keys = keyring("k1", &["k1"]);
+        let envelope = keys.encrypt_value(&json!("secret"), "p1", "a.b").unwrap();
+        assert_eq!(
+            keys.decrypt_value(&envelope, "p1", "a.b").unwrap(),
+            json!("secret")
+        );
+        assert!(keys.decrypt_value(&envelope, "p2", "a.b").is_err());
+        assert!(keys.decrypt_value(&envelope, "p1", "a.c").is_err());
+        // The length prefix keeps id/path splits from colliding.
+        assert!(keys.decrypt_value(&envelope, "p1a", ".b").is_err());
+    }
+
+    #[test]
+    fn envelope_moved_to_another_field_fails() {
+        let keys = keyring("k1", &["k1"]);
+        let mut value = profile();
+        keys.encrypt_paths(&mut value, "p1", &paths()).unwrap();
+        let moved = value["tags"]["profile"]["contacts"][1]["phone"].clone();
+        value["tags"]["profile"]["contacts"][0]["phone"] = moved;
+        assert!(keys.decrypt_all(&mut value, "p1", "").is_err());
+    }
+
+    #[test]
+    fn envelopes_without_the_current_version_are_rejected() {
+        let keys = keyring("k1", &["k1"]);
+        let mut envelope = keys.encrypt_value(&json!("secret"), "p1", "a").unwrap();
+        envelope.as_object_mut().unwrap().remove("v");
+        assert!(keys.decrypt_value(&envelope, "p1", "a").is_err());
+        assert!(keys.rewrap_all(&mut envelope).is_err());
+
+        envelope["v"] = json!(ENVELOPE_VERSION + 1);
+        assert!(keys.decrypt_value(&envelope, "p1", "a").is_err());
+    }
+
+    #[test]
+    fn unknown_kid_fails_to_decrypt() {
+        let old = keyring("k1", &["k1"]);
+        let envelope = old.encrypt_value(&json!("secret"), "p1", "a").unwrap();
+        let new = keyring("k2", &["k2"]);
+        let err = new.decrypt_value(&envelope, "p1", "a").unwrap_err();
+        assert!(err.to_string().contains("unknown key id k1"));
+    }
+
+    #[test]
+    fn rewrap_all_moves_envelopes_to_the_active_key() {
+        let old = keyring("k1", &["k1"]);
+        let mut value = profile();
+        old.encrypt_paths(&mut value, "p1", &paths()).unwrap();
+
+        let rotated = keyring("k2", &["k1", "k2"]);
+        assert_eq!(rotated.rewrap_all(&mut value).unwrap(), 3);
+        assert_eq!(rotated.rewrap_all(&mut value).unwrap(), 0);
+        assert_eq!(value["tags"]["profile"]["whoIAm"]["email"]["kid"], "k2");
+
+        // The old key is no longer needed once every envelope is rewrapped.
+        let retired = keyring("k2", &["k2"]);
+        assert_eq!(retired.decrypt_all(&mut value, "p1", "").unwrap(), 3);
+        assert_eq!(value, profile());
+    }
+
+    #[test]
+    fn mask_envelopes_hides_ciphertext() {
+        let keys = keyring("k1", &["k1"]);
+        let mut value = profile();
+        keys.encrypt_paths(&mut value, "p1", &paths()).unwrap();
+        assert_eq!(mask_envelopes(&mut value), 3);
+        assert_eq!(value["tags"]["profile"]["whoIAm"]["email"], MASK);
+    }
+}
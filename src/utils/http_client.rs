@@ -1,11 +1,32 @@
+use crate::utils::telemetry::inject_trace_context;
 use anyhow::Result;
-use reqwest::Client;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Response};
 use serde_json::Value;
-use tracing::{error, info};
+use tracing::{error, field::Empty, info, instrument, Span};
+
+/// Sends `payload` with the current trace context in `traceparent`.
+#[instrument(
+    name = "http.post",
+    skip(payload),
+    fields(otel.kind = "client", http.response.status_code = Empty)
+)]
+async fn send(url: &str, payload: &Value) -> reqwest::Result<Response> {
+    let mut headers = HeaderMap::new();
+    inject_trace_context(&mut headers);
+
+    let res = Client::new()
+        .post(url)
+        .headers(headers)
+        .json(payload)
+        .send()
+        .await?;
+    Span::current().record("http.response.status_code", res.status().as_u16());
+    Ok(res)
+}
 
 pub async fn post_json(url: &str, payload: Value) -> Result<Value> {
-    let client = Client::new();
-    let res = client.post(url).json(&payload).send().await?;
+    let res = send(url, &payload).await?;
     info!("Sending POST request to {} ", url);
 
     let status = res.status();
@@ -26,3 +47,22 @@ pub async fn post_json(url: &str, payload: Value) -> Result<Value> {
         ))
     }
 }
+
+pub async fn post_json_expect_success(url: &str, payload: Value) -> Result<()> {
+    let res = send(url, &payload).await?;
+    info!("Sending POST request to {} ", url);
+
+    let status = res.status();
+
+    if status.is_success() {
+        Ok(())
+    } else {
+        let body_text = res.text().await.unwrap_or_default();
+        error!("Failed with status {}: {}", status, body_text);
+        Err(anyhow::anyhow!(
+            "Failed with status {}: {}",
+            status,
+            body_text
+        ))
+    }
+}
@@ -0,0 +1,351 @@
+use crate::config::JwtConfig;
+use crate::models::auth::{AuthMethod, Caller, CallerRole, Scope};
+
+use anyhow::{anyhow, bail, Context};
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{AlgorithmParameters, EllipticCurve, JwkSet, KeyAlgorithm},
+    Algorithm, DecodingKey, Validation,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+/// Minimum gap between JWKS reload attempts, failed ones included, so a
+/// stream of forged tokens or a down key source is not hammered.
+const MIN_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+struct KeySet {
+    keys: HashMap<String, (DecodingKey, Algorithm)>,
+    loaded_at: Option<Instant>,
+    attempted_at: Option<Instant>,
+}
+
+impl KeySet {
+    /// Reload when the set is older than `refresh` or lacks `kid`, but not
+    /// within `MIN_RELOAD_INTERVAL` of the last attempt.
+    fn needs_reload(&self, kid: &str, refresh: Duration) -> bool {
+        if self
+            .attempted_at
+            .is_some_and(|t| t.elapsed() < MIN_RELOAD_INTERVAL)
+        {
+            return false;
+        }
+        let stale = self.loaded_at.is_none_or(|t| t.elapsed() >= refresh);
+        stale || !self.keys.contains_key(kid)
+    }
+}
+
+pub struct JwtVerifier {
+    config: JwtConfig,
+    keys: RwLock<KeySet>,
+    /// Held for the whole of a reload so concurrent lookups share one fetch.
+    reload_lock: Mutex<()>,
+}
+
+impl JwtVerifier {
+    pub async fn new(config: JwtConfig) -> anyhow::Result<Self> {
+        if config.jwks_path.is_none() && config.jwks_url.is_none() {
+            bail!("auth.jwt requires either jwks_path or jwks_url");
+        }
+
+        let verifier = Self {
+            config,
+            keys: RwLock::new(KeySet::default()),
+            reload_lock: Mutex::new(()),
+        };
+        verifier.reload().await?;
+        Ok(verifier)
+    }
+
+    async fn fetch_jwks(&self) -> anyhow::Result<JwkSet> {
+        let raw = match (&self.config.jwks_path, &self.config.jwks_url) {
+            (Some(path), _) => tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("reading JWKS file {path}"))?,
+            (None, Some(url)) => {
+                reqwest::get(url)
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .with_context(|| format!("fetching JWKS from {url}"))?
+                    .text()
+                    .await?
+            }
+            (None, None) => bail!("no JWKS source configured"),
+        };
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    /// Replaces the key set from the JWKS source. The attempt is recorded
+    /// even when it fails, which rate-limits retries.
+    async fn reload(&self) -> anyhow::Result<()> {
+        let fetched = self.fetch_jwks().await;
+        let now = Instant::now();
+        let mut set = self.keys.write().await;
+        set.attempted_at = Some(now);
+        let keys = parse_keys(&fetched?);
+
+        info!("🔐 Loaded {} JWKS signing keys", keys.len());
+        set.keys = keys;
+        set.loaded_at = Some(now);
+        Ok(())
+    }
+
+    async fn lookup(&self, kid: &str) -> Option<(DecodingKey, Algorithm)> {
+        let refresh = Duration::from_secs(self.config.refresh_seconds);
+        {
+            let set = self.keys.read().await;
+            if !set.needs_reload(kid, refresh) {
+                return set.keys.get(kid).cloned();
+            }
+        }
+
+        // Lookups that queued behind a reload find it done and skip their own.
+        let _reloading = self.reload_lock.lock().await;
+        if self.keys.read().await.needs_reload(kid, refresh) {
+            if let Err(e) = self.reload().await {
+                // Keys from the last good load stay in use.
+                warn!("JWKS reload failed: {:?}", e);
+            }
+        }
+        self.keys.read().await.keys.get(kid).cloned()
+    }
+
+    /// Validates `token` and maps its claims to a `Caller`.
+    pub async fn verify(&self, token: &str) -> anyhow::Result<Caller> {
+        let header = decode_header(token)?;
+        let kid = header.kid.ok_or_else(|| anyhow!("token has no kid"))?;
+        let (key, alg) = self
+            .lookup(&kid)
+            .await
+            .ok_or_else(|| anyhow!("unknown signing key {kid}"))?;
+        if header.alg != alg {
+            bail!(
+                "token alg {:?} does not match key alg {:?}",
+                header.alg,
+                alg
+            );
+        }
+
+        let mut validation = Validation::new(alg);
+        validation.leeway = self.config.leeway_seconds;
+        if let Some(issuer) = &self.config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        if self.config.audience.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&self.config.audience);
+        }
+
+        let claims = decode::<Value>(token, &key, &validation)?.claims;
+        self.caller_from_claims(&claims)
+    }
+
+    fn caller_from_claims(&self, claims: &Value) -> anyhow::Result<Caller> {
+        let subject = claims
+            .get(&self.config.subject_claim)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow!("missing '{}' claim", self.config.subject_claim))?;
+        let id = format!("jwt:{subject}");
+
+        let values: Vec<&str> = self
+            .config
+            .scope_claims
+            .iter()
+            .filter_map(|name| claims.get(name))
+            .flat_map(|v| match v {
+                Value::String(s) => s.split_whitespace().collect::<Vec<_>>(),
+                Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+
+        let mut scopes: Vec<Scope> = Vec::new();
+        for value in &values {
+            let mapped = match self.config.scope_mapping.get(*value) {
+                Some(mapped) => mapped.clone(),
+                None => Scope::parse(value).into_iter().collect(),
+            };
+            for scope in mapped {
+                if !scopes.contains(&scope) {
+                    scopes.push(scope);
+                }
+            }
+        }
+
+        let role = if values
+            .iter()
+            .any(|v| self.config.privileged_values.iter().any(|p| p == v))
+        {
+            CallerRole::Privileged
+        } else {
+            CallerRole::Standard
+        };
+
+        Ok(Caller {
+            id,
+            auth_method: AuthMethod::Bearer,
+            role,
+            scopes,
+            rate_limit_per_minute: None,
+        })
+    }
+}
+
+/// Signing keys by `kid`. Keys without a `kid` or with an algorithm other
+/// than RS256 or ES256 are skipped.
+fn parse_keys(jwks: &JwkSet) -> HashMap<String, (DecodingKey, Algorithm)> {
+    let mut keys = HashMap::new();
+    for jwk in &jwks.keys {
+        let Some(kid) = jwk.common.key_id.clone() else {
+            continue;
+        };
+        let alg = match (&jwk.algorithm, jwk.common.key_algorithm) {
+            (AlgorithmParameters::RSA(_), None | Some(KeyAlgorithm::RS256)) => Algorithm::RS256,
+            (AlgorithmParameters::EllipticCurve(ec), None | Some(KeyAlgorithm::ES256))
+                if ec.curve == EllipticCurve::P256 =>
+            {
+                Algorithm::ES256
+            }
+            _ => {
+                warn!("Skipping JWK {}: only RS256 and ES256 are accepted", kid);
+                continue;
+            }
+        };
+        match DecodingKey::from_jwk(jwk) {
+            Ok(key) => {
+                keys.insert(kid, (key, alg));
+            }
+            Err(e) => warn!("Skipping invalid JWK {}: {}", kid, e),
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn verifier(config: Value) -> JwtVerifier {
+        JwtVerifier {
+            config: serde_json::from_value(config).unwrap(),
+            keys: RwLock::new(KeySet::default()),
+            reload_lock: Mutex::new(()),
+        }
+    }
+
+    #[test]
+    fn caller_from_claims_namespaces_the_subject() {
+        let v = verifier(json!({}));
+        let caller = v
+            .caller_from_claims(&json!({ "sub": "user-1", "scope": "search details" }))
+            .unwrap();
+        assert_eq!(caller.id, "jwt:user-1");
+        assert_eq!(caller.auth_method, AuthMethod::Bearer);
+        assert_eq!(caller.role, CallerRole::Standard);
+        assert_eq!(caller.scopes, vec![Scope::Search, Scope::Details]);
+
+        assert!(v.caller_from_claims(&json!({ "scope": "search" })).is_err());
+        assert!(v.caller_from_claims(&json!({ "sub": "" })).is_err());
+
+        let custom = verifier(json!({ "subject_claim": "email" }));
+        let caller = custom
+            .caller_from_claims(&json!({ "sub": "x", "email": "a@example.com" }))
+            .unwrap();
+        assert_eq!(caller.id, "jwt:a@example.com");
+    }
+
+    #[test]
+    fn scope_mapping_and_privileged_values() {
+        let v = verifier(json!({
+            "scope_mapping": {
+                "recruiter": ["search", "details"],
+                "ops": ["admin", "search"]
+            },
+            "privileged_values": ["ops"]
+        }));
+
+        // Arrays and strings across claims, in scope_claims order; mapped
+        // and direct scopes dedupe.
+        let caller = v
+            .caller_from_claims(&json!({
+                "sub": "u",
+                "roles": ["recruiter", "unknown"],
+                "scp": "search insights"
+            }))
+            .unwrap();
+        assert_eq!(
+            caller.scopes,
+            vec![Scope::Search, Scope::Insights, Scope::Details]
+        );
+        assert_eq!(caller.role, CallerRole::Standard);
+
+        let caller = v
+            .caller_from_claims(&json!({ "sub": "u", "roles": "ops" }))
+            .unwrap();
+        assert_eq!(caller.scopes, vec![Scope::Admin, Scope::Search]);
+        assert_eq!(caller.role, CallerRole::Privileged);
+
+        // Claims outside scope_claims are ignored.
+        let caller = v
+            .caller_from_claims(&json!({ "sub": "u", "groups": ["ops"] }))
+            .unwrap();
+        assert!(caller.scopes.is_empty());
+        assert_eq!(caller.role, CallerRole::Standard);
+    }
+
+    #[test]
+    fn needs_reload_respects_the_retry_interval() {
+        let refresh = Duration::from_secs(3600);
+        let never_loaded = KeySet::default();
+        assert!(never_loaded.needs_reload("k1", refresh));
+
+        let failed_just_now = KeySet {
+            attempted_at: Some(Instant::now()),
+            ..KeySet::default()
+        };
+        assert!(!failed_just_now.needs_reload("k1", refresh));
+
+        let loaded = KeySet {
+            loaded_at: Some(Instant::now() - MIN_RELOAD_INTERVAL),
+            attempted_at: Some(Instant::now() - MIN_RELOAD_INTERVAL),
+            ..KeySet::default()
+        };
+        // Fresh, but the kid is unknown.
+        assert!(loaded.needs_reload("k1", refresh));
+        assert!(loaded.needs_reload("k1", Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    async fn failed_reload_is_not_retried_immediately() {
+        let v = verifier(json!({ "jwks_path": "/nonexistent/jwks.json" }));
+        assert!(v.lookup("k1").await.is_none());
+
+        let set = v.keys.read().await;
+        assert!(set.attempted_at.is_some());
+        assert!(set.loaded_at.is_none());
+        assert!(!set.needs_reload("k1", Duration::from_secs(3600)));
+    }
+
+    #[tokio::test]
+    async fn concurrent_lookups_share_one_reload() {
+        let path = std::env::temp_dir().join(format!("jwks-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, r#"{"keys":[]}"#).unwrap();
+        let v = verifier(json!({ "jwks_path": path.to_str().unwrap() }));
+
+        let lookups = (0..8).map(|_| v.lookup("k1"));
+        let results = futures::future::join_all(lookups).await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(results.iter().all(Option::is_none));
+        let set = v.keys.read().await;
+        // Every lookup after the first found the attempt already made.
+        assert_eq!(set.loaded_at, set.attempted_at);
+    }
+}
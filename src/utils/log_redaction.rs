@@ -0,0 +1,111 @@
+use crate::config::LogRedactionConfig;
+use regex::Regex;
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::sync::Arc;
+use tracing_subscriber::fmt::MakeWriter;
+
+const EMAIL: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}";
+
+// International numbers with a country code, and ten-digit mobile numbers
+// with or without a bare 91 prefix. Epoch seconds start with 1 and so do
+// not match the latter.
+const PHONE: &str = r"\+\d{1,3}[\s-]?\d[\d\s-]{5,13}\d|\b(?:91)?[6-9]\d{9}\b";
+
+// A quote, optionally backslash-escaped as it is inside JSON log messages.
+const QUOTE: &str = r#"\\?""#;
+
+/// Masks personal data in formatted log lines, whatever produced them:
+/// `{:?}` dumps of payloads, JSON log messages or span fields.
+pub struct LogRedactor {
+    rules: Vec<(Regex, String)>,
+}
+
+impl LogRedactor {
+    pub fn new(config: &LogRedactionConfig) -> Result<Self, regex::Error> {
+        let mut rules = vec![
+            (Regex::new(EMAIL)?, "[email]".to_string()),
+            (Regex::new(PHONE)?, "[phone]".to_string()),
+        ];
+
+        for field in &config.name_fields {
+            rules.push((Regex::new(&field_pattern(field))?, "${1}[name]".to_string()));
+        }
+        for pattern in &config.patterns {
+            rules.push((Regex::new(pattern)?, "[redacted]".to_string()));
+        }
+
+        Ok(Self { rules })
+    }
+
+    pub fn redact<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        let mut line = Cow::Borrowed(line);
+        for (regex, replacement) in &self.rules {
+            if let Cow::Owned(replaced) = regex.replace_all(&line, replacement.as_str()) {
+                line = Cow::Owned(replaced);
+            }
+        }
+        line
+    }
+}
+
+/// Matches the string value of `key`, or of `key` inside a `parent` object
+/// for `parent.key`, in JSON, `serde_json::Value` debug output and derived
+/// `Debug` output. The key and everything before the value is group 1.
+fn field_pattern(field: &str) -> String {
+    let key = |name: &str| format!(r#"(?:{QUOTE})?\b{}(?:{QUOTE})?"#, regex::escape(name));
+    let value = format!(r#"\s*[:=]\s*(?:String\()?{QUOTE})[^"\\]*"#);
+
+    match field.rsplit_once('.') {
+        Some((parent, name)) => format!(
+            r"({}\s*[:=]\s*(?:\w+\s*)?\{{(?:[^{{}}]|\{{[^{{}}]*\}})*?{}{value}",
+            key(parent),
+            key(name),
+        ),
+        None => format!(r"({}{value}", key(field)),
+    }
+}
+
+/// Wraps a `MakeWriter` so every line is redacted before it is written.
+pub struct Redacting<M> {
+    inner: M,
+    redactor: Option<Arc<LogRedactor>>,
+}
+
+impl<M> Redacting<M> {
+    pub fn new(inner: M, redactor: Option<Arc<LogRedactor>>) -> Self {
+        Self { inner, redactor }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacting<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+            redactor: self.redactor.clone(),
+        }
+    }
+}
+
+pub struct RedactingWriter<W> {
+    inner: W,
+    redactor: Option<Arc<LogRedactor>>,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(redactor) = &self.redactor else {
+            return self.inner.write(buf);
+        };
+
+        let text = String::from_utf8_lossy(buf);
+        self.inner.write_all(redactor.redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -1,39 +1,131 @@
+use crate::config::{AppConfig, LogFormat, LogRotation};
+use crate::utils::log_redaction::{LogRedactor, Redacting};
+use crate::utils::telemetry::{install_propagator, tracer_provider};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use std::sync::Arc;
 use tracing::info;
-use tracing_appender::{non_blocking::WorkerGuard, rolling};
-use tracing_subscriber::{fmt, fmt::time::UtcTime, prelude::*, EnvFilter};
-pub fn setup_logging(log_dir: &str, svc: &str) -> WorkerGuard {
-    let log_file_name = format!("{}.log", svc);
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{
+    fmt, fmt::time::UtcTime, fmt::MakeWriter, prelude::*, registry::Registry, EnvFilter, Layer,
+};
 
-    let (file_writer, file_guard) =
-        tracing_appender::non_blocking(rolling::daily(log_dir, log_file_name));
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
 
-    let file_layer = fmt::layer()
-        .with_writer(file_writer)
-        .json()
-        .with_timer(UtcTime::rfc_3339())
-        .with_target(true)
-        .with_thread_ids(false)
-        .with_filter(EnvFilter::new("info"));
+/// Keeps the log writer and span exporter alive; dropping it flushes both.
+pub struct LoggingGuard {
+    _file: Option<WorkerGuard>,
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for LoggingGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.tracer_provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush spans: {e:?}");
+            }
+        }
+    }
+}
+
+/// `RUST_LOG` when set, otherwise `logging.level` (or debug with `debug:
+/// true`) plus the per-target levels.
+fn env_filter(config: &AppConfig) -> EnvFilter {
+    match std::env::var("RUST_LOG") {
+        Ok(directives) if !directives.trim().is_empty() => EnvFilter::new(directives),
+        _ => EnvFilter::new(config.logging.filter_directives(config.debug)),
+    }
+}
 
-    let console_layer = fmt::layer()
-        .compact()
+fn fmt_layer<W>(format: LogFormat, writer: W, ansi: bool, filter: EnvFilter) -> BoxedLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi)
         .with_timer(UtcTime::rfc_3339())
         .with_target(true)
-        .with_thread_ids(false)
-        .with_filter(EnvFilter::new("info"));
+        .with_thread_ids(false);
+
+    match format {
+        LogFormat::Json => layer.json().with_filter(filter).boxed(),
+        LogFormat::Pretty => layer.pretty().with_filter(filter).boxed(),
+        LogFormat::Compact => layer.compact().with_filter(filter).boxed(),
+    }
+}
+
+pub fn setup_logging(config: &AppConfig) -> anyhow::Result<LoggingGuard> {
+    let logging = &config.logging;
+    let redactor = if logging.redaction.enabled {
+        Some(Arc::new(LogRedactor::new(&logging.redaction)?))
+    } else {
+        None
+    };
+
+    let mut layers: Vec<BoxedLayer> = Vec::new();
+
+    if logging.stdout {
+        layers.push(fmt_layer(
+            logging.format,
+            Redacting::new(std::io::stdout, redactor.clone()),
+            true,
+            env_filter(config),
+        ));
+    }
+
+    let file_guard = if logging.file.enabled {
+        let file = &logging.file;
+        let rotation = match file.rotation {
+            LogRotation::Minutely => Rotation::MINUTELY,
+            LogRotation::Hourly => Rotation::HOURLY,
+            LogRotation::Daily => Rotation::DAILY,
+            LogRotation::Never => Rotation::NEVER,
+        };
+        let mut appender = RollingFileAppender::builder()
+            .rotation(rotation)
+            .filename_prefix(&file.prefix)
+            .filename_suffix("log");
+        if file.max_files > 0 {
+            appender = appender.max_log_files(file.max_files);
+        }
+
+        let (file_writer, file_guard) = tracing_appender::non_blocking(appender.build(&file.dir)?);
+        layers.push(fmt_layer(
+            file.format,
+            Redacting::new(file_writer, redactor),
+            false,
+            env_filter(config),
+        ));
+        Some(file_guard)
+    } else {
+        None
+    };
+
+    install_propagator();
+    let tracer_provider = tracer_provider(&config.telemetry)?;
+    if let Some(provider) = &tracer_provider {
+        layers.push(
+            tracing_opentelemetry::layer()
+                .with_tracer(provider.tracer(config.telemetry.service_name.clone()))
+                .with_filter(env_filter(config))
+                .boxed(),
+        );
+    }
 
-    tracing::subscriber::set_global_default(
-        tracing_subscriber::registry()
-            .with(file_layer)
-            .with(console_layer),
-    )
-    .expect("Failed to set global subscriber");
+    tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layers))?;
 
-    file_guard
+    Ok(LoggingGuard {
+        _file: file_guard,
+        tracer_provider,
+    })
 }
 
 pub fn log_cron_job(icon: &str, message: &str) {
-    let content = format!("{} {}", icon, message);
+    let content = format!("{icon} {message}");
 
     let width = 44;
     let padded = format!("{:<width$}", content, width = width - 2);
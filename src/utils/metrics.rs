@@ -0,0 +1,132 @@
+use crate::state::AppState;
+use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Latency buckets in seconds, shared by every `*_seconds` histogram.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0,
+];
+
+/// Beckn actions reported as-is in the `action` label. Anything else in a
+/// webhook path is reported as "other" so callers cannot grow the label set.
+const BECKN_ACTIONS: &[&str] = &[
+    "search",
+    "select",
+    "init",
+    "confirm",
+    "status",
+    "track",
+    "cancel",
+    "update",
+    "rating",
+    "support",
+    "on_search",
+    "on_select",
+    "on_init",
+    "on_confirm",
+    "on_status",
+    "on_track",
+    "on_cancel",
+    "on_update",
+    "on_rating",
+    "on_support",
+];
+
+/// Installs the global Prometheus recorder. Call once per process.
+pub fn install() -> Result<PrometheusHandle, BuildError> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".into()), LATENCY_BUCKETS)?
+        .install_recorder()
+}
+
+pub fn action_label(action: &str) -> &'static str {
+    BECKN_ACTIONS
+        .iter()
+        .find(|known| **known == action)
+        .copied()
+        .unwrap_or("other")
+}
+
+fn outcome(ok: bool) -> &'static str {
+    if ok {
+        "success"
+    } else {
+        "failure"
+    }
+}
+
+pub fn record_request(method: &str, route: &str, action: &str, status: u16, elapsed: Duration) {
+    let labels = [
+        ("method", method.to_string()),
+        ("route", route.to_string()),
+        ("action", action.to_string()),
+    ];
+    let mut counted = labels.to_vec();
+    counted.push(("status", status.to_string()));
+
+    counter!("http_requests_total", &counted).increment(1);
+    histogram!("http_request_duration_seconds", &labels).record(elapsed.as_secs_f64());
+}
+
+/// Outcome of generating a response and delivering the `on_*` callback.
+pub fn record_callback(action: &str, ok: bool) {
+    counter!(
+        "beckn_callbacks_total",
+        "action" => action_label(action),
+        "outcome" => outcome(ok)
+    )
+    .increment(1);
+}
+
+pub fn record_provider_db(path: &str, ok: bool, elapsed: Duration) {
+    let path = path.trim_start_matches('/').to_string();
+    histogram!(
+        "provider_db_request_duration_seconds",
+        "path" => path,
+        "outcome" => outcome(ok)
+    )
+    .record(elapsed.as_secs_f64());
+}
+
+pub fn record_profiles_ingested(bpp_id: &str, count: usize, ok: bool) {
+    counter!(
+        "profiles_ingested_total",
+        "bpp_id" => bpp_id.to_string(),
+        "outcome" => outcome(ok)
+    )
+    .increment(count as u64);
+}
+
+/// Stale rows removed after a complete sync. Also marks the sync as
+/// finished so a dead sync shows up as a stale timestamp.
+pub fn record_sync_completed(bpp_id: &str, deleted: u64) {
+    counter!("profiles_stale_deleted_total", "bpp_id" => bpp_id.to_string()).increment(deleted);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    gauge!(
+        "profile_sync_last_completed_timestamp_seconds",
+        "bpp_id" => bpp_id.to_string()
+    )
+    .set(now.as_secs_f64());
+}
+
+pub fn record_cron_run(task: &'static str, elapsed: Duration) {
+    counter!("cron_runs_total", "task" => task).increment(1);
+    histogram!("cron_run_duration_seconds", "task" => task).record(elapsed.as_secs_f64());
+}
+
+/// Samples connection pool gauges. Called on every scrape.
+pub fn record_pool_stats(app_state: &AppState) {
+    let redis = app_state.redis_pool.status();
+    gauge!("redis_pool_connections", "state" => "open").set(redis.size as f64);
+    gauge!("redis_pool_connections", "state" => "idle").set(redis.available as f64);
+    gauge!("redis_pool_max_connections").set(redis.max_size as f64);
+    gauge!("redis_pool_waiting").set(redis.waiting as f64);
+
+    let db = &app_state.db_pool;
+    gauge!("postgres_pool_connections", "state" => "open").set(db.size() as f64);
+    gauge!("postgres_pool_connections", "state" => "idle").set(db.num_idle() as f64);
+    gauge!("postgres_pool_max_connections").set(db.options().get_max_connections() as f64);
+}
@@ -1,6 +1,16 @@
+pub mod api_keys;
+pub mod audit;
 pub mod cron;
+pub mod encryption;
 pub mod http_client;
+pub mod jwt;
+pub mod log_redaction;
 pub mod logging;
+pub mod metrics;
 pub mod mock_responses;
 pub mod payload_generator;
+pub mod payload_log;
+pub mod rate_limit;
+pub mod redaction;
 pub mod shared;
+pub mod telemetry;
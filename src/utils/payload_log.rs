@@ -0,0 +1,64 @@
+use crate::config::PayloadLogConfig;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::{debug, enabled, info, Level};
+
+/// Whether `txn_id` falls in the sampled share of transactions. Hashing
+/// keeps the decision stable across calls and instances.
+fn sampled(txn_id: &str, percent: f64) -> bool {
+    if percent <= 0.0 {
+        return false;
+    }
+    let digest = Sha256::digest(txn_id.as_bytes());
+    let bucket = u16::from_be_bytes([digest[0], digest[1]]) % 10_000;
+    f64::from(bucket) < percent * 100.0
+}
+
+fn truncate(body: &str, max_bytes: usize) -> (&str, usize) {
+    if body.len() <= max_bytes {
+        return (body, 0);
+    }
+    let mut end = max_bytes;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&body[..end], body.len() - end)
+}
+
+/// Logs a Beckn payload under the `payload` target. With that target at
+/// debug the full body is logged at debug. Otherwise it is logged at info,
+/// cut to `max_bytes` unless the transaction is sampled or allow-listed.
+pub fn log_payload(label: &str, payload: &Value, policy: &PayloadLogConfig) {
+    let txn_id = payload
+        .pointer("/context/transaction_id")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let body = payload.to_string();
+
+    if enabled!(target: "payload", Level::DEBUG) {
+        debug!(target: "payload", transaction_id = txn_id, bytes = body.len(), "{}: {}", label, body);
+        return;
+    }
+
+    let full = policy.transaction_ids.iter().any(|id| id == txn_id)
+        || sampled(txn_id, policy.sample_percent);
+    if full {
+        info!(target: "payload", transaction_id = txn_id, bytes = body.len(), "{}: {}", label, body);
+        return;
+    }
+
+    let (shown, cut) = truncate(&body, policy.max_bytes);
+    if cut > 0 {
+        info!(
+            target: "payload",
+            transaction_id = txn_id,
+            bytes = body.len(),
+            "{}: {}… ({} bytes truncated)",
+            label,
+            shown,
+            cut
+        );
+    } else {
+        info!(target: "payload", transaction_id = txn_id, bytes = body.len(), "{}: {}", label, body);
+    }
+}
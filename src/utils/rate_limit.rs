@@ -0,0 +1,200 @@
+use crate::config::RateLimit;
+
+use deadpool_redis::Pool;
+use redis::{AsyncCommands, Script};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+pub const RATE_LIMIT_PREFIX: &str = "ratelimit:";
+
+// Token bucket kept in a Redis hash so every instance shares the same budget.
+// The hash also records the bucket's shape and allow/reject counters so
+// usage can be reported without knowing the config that created it.
+// Returns {allowed, remaining_tokens}.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_per_ms = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1])
+local ts = tonumber(bucket[2])
+if tokens == nil or ts == nil then
+  tokens = capacity
+  ts = now
+end
+
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * refill_per_ms)
+
+local allowed = 0
+if tokens >= 1 then
+  tokens = tokens - 1
+  allowed = 1
+  redis.call('HINCRBY', KEYS[1], 'allowed', 1)
+else
+  redis.call('HINCRBY', KEYS[1], 'rejected', 1)
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now,
+  'capacity', capacity, 'refill', ARGV[2])
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity / refill_per_ms) + 1000)
+
+return {allowed, math.floor(tokens)}
+"#;
+
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub remaining: u64,
+    pub retry_after_secs: u64,
+}
+
+fn now_ms() -> anyhow::Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64)
+}
+
+/// Capacity and per-millisecond refill rate passed to the bucket script.
+/// Both inputs are clamped to 1 so a zero in config cannot stall a bucket.
+fn bucket_params(capacity: u32, period_secs: u64) -> (u32, f64) {
+    let capacity = capacity.max(1);
+    let refill_per_ms = capacity as f64 / (period_secs.max(1) as f64 * 1000.0);
+    (capacity, refill_per_ms)
+}
+
+/// Whole seconds until an empty bucket holds one token again.
+fn retry_after_secs(refill_per_ms: f64) -> u64 {
+    (1.0 / (refill_per_ms * 1000.0)).ceil() as u64
+}
+
+/// Takes one token from the bucket at `key`, which holds up to `capacity`
+/// tokens and refills completely every `period_secs`.
+pub async fn take_token(
+    redis_pool: &Pool,
+    key: &str,
+    capacity: u32,
+    period_secs: u64,
+) -> anyhow::Result<RateLimitDecision> {
+    let (capacity, refill_per_ms) = bucket_params(capacity, period_secs);
+
+    let mut conn = redis_pool.get().await?;
+    let (allowed, remaining): (i64, i64) = Script::new(TOKEN_BUCKET_SCRIPT)
+        .key(key)
+        .arg(capacity)
+        .arg(refill_per_ms.to_string())
+        .arg(now_ms()?)
+        .invoke_async(&mut conn)
+        .await?;
+
+    Ok(RateLimitDecision {
+        allowed: allowed == 1,
+        remaining: remaining.max(0) as u64,
+        retry_after_secs: retry_after_secs(refill_per_ms),
+    })
+}
+
+/// Applies `limit` to `bucket` (relative to `ratelimit:`) and returns the
+/// decision only when the request must be rejected. Redis failures are
+/// logged and the request is let through.
+pub async fn enforce(
+    redis_pool: &Pool,
+    bucket: &str,
+    limit: &RateLimit,
+) -> Option<RateLimitDecision> {
+    let key = format!("{RATE_LIMIT_PREFIX}{bucket}");
+    match take_token(redis_pool, &key, limit.requests, limit.period_seconds).await {
+        Ok(decision) if !decision.allowed => Some(decision),
+        Ok(_) => None,
+        Err(e) => {
+            warn!(
+                "Rate limit check failed for {}, allowing request: {:?}",
+                key, e
+            );
+            None
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BucketUsage {
+    pub bucket: String,
+    pub capacity: u64,
+    pub remaining: u64,
+    pub period_seconds: u64,
+    pub allowed: u64,
+    pub rejected: u64,
+}
+
+/// Reports every live bucket, with remaining tokens refilled up to now.
+pub async fn bucket_usage(redis_pool: &Pool) -> anyhow::Result<Vec<BucketUsage>> {
+    let mut conn = redis_pool.get().await?;
+    let keys: Vec<String> = {
+        let mut iter = conn
+            .scan_match::<_, String>(format!("{RATE_LIMIT_PREFIX}*"))
+            .await?;
+        let mut keys = Vec::new();
+        while let Some(key) = iter.next_item().await {
+            keys.push(key);
+        }
+        keys
+    };
+
+    let now = now_ms()? as f64;
+    let mut usage = Vec::with_capacity(keys.len());
+    for key in keys {
+        let fields: HashMap<String, String> = conn.hgetall(&key).await?;
+        let num = |name: &str| fields.get(name).and_then(|v| v.parse::<f64>().ok());
+
+        let (Some(capacity), Some(refill), Some(tokens), Some(ts)) =
+            (num("capacity"), num("refill"), num("tokens"), num("ts"))
+        else {
+            continue;
+        };
+
+        let remaining = (tokens + (now - ts).max(0.0) * refill).min(capacity);
+        usage.push(BucketUsage {
+            bucket: key.trim_start_matches(RATE_LIMIT_PREFIX).to_string(),
+            capacity: capacity as u64,
+            remaining: remaining.floor() as u64,
+            period_seconds: if refill > 0.0 {
+                (capacity / refill / 1000.0).round() as u64
+            } else {
+                0
+            },
+            allowed: num("allowed").unwrap_or(0.0) as u64,
+            rejected: num("rejected").unwrap_or(0.0) as u64,
+        });
+    }
+
+    usage.sort_by(|a, b| a.bucket.cmp(&b.bucket));
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_params_refill_the_whole_capacity_per_period() {
+        let (capacity, refill_per_ms) = bucket_params(120, 60);
+        assert_eq!(capacity, 120);
+        assert!((refill_per_ms * 60_000.0 - 120.0).abs() < 1e-9);
+        assert_eq!(retry_after_secs(refill_per_ms), 1);
+
+        // One request per 10 minutes: a token every 600s.
+        let (_, slow) = bucket_params(1, 600);
+        assert_eq!(retry_after_secs(slow), 600);
+
+        // Rounds up rather than advertising a retry that would still fail.
+        let (_, uneven) = bucket_params(7, 60);
+        assert_eq!(retry_after_secs(uneven), 9);
+    }
+
+    #[test]
+    fn bucket_params_clamp_zero_config() {
+        let (capacity, refill_per_ms) = bucket_params(0, 0);
+        assert_eq!(capacity, 1);
+        assert!((refill_per_ms - 0.001).abs() < 1e-12);
+        assert_eq!(retry_after_secs(refill_per_ms), 1);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::config::{RedactionAction, RedactionConfig, RedactionRule};
+use crate::models::auth::CallerRole;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+const MASK: &str = "****";
+
+/// The redaction rules that apply to one caller role, for code that turns
+/// stored profiles into output away from the request handler.
+#[derive(Debug, Clone)]
+pub struct RedactionView {
+    rules: Vec<RedactionRule>,
+    salt: String,
+    role: CallerRole,
+}
+
+impl RedactionView {
+    pub fn new(policy: &RedactionConfig, role: CallerRole) -> Self {
+        Self {
+            rules: policy
+                .rules
+                .iter()
+                .filter(|r| r.roles.contains(&role))
+                .cloned()
+                .collect(),
+            salt: policy.hash_salt.clone(),
+            role,
+        }
+    }
+
+    /// Redacts a `tags.profile` object in place.
+    pub fn apply(&self, profile: &mut Value) -> bool {
+        redact(profile, &self.rules, self.role, &self.salt)
+    }
+}
+
+/// Applies every rule that targets `role` to `value` in place. Paths are
+/// dot-separated and `*` matches every element of an array or object.
+pub fn redact(value: &mut Value, rules: &[RedactionRule], role: CallerRole, salt: &str) -> bool {
+    let mut redacted = false;
+    for rule in rules.iter().filter(|r| r.roles.contains(&role)) {
+        let segments: Vec<&str> = rule.path.split('.').filter(|s| !s.is_empty()).collect();
+        redacted |= apply_at_path(value, &segments, rule.action, salt);
+    }
+    redacted
+}
+
+fn apply_at_path(
+    value: &mut Value,
+    segments: &[&str],
+    action: RedactionAction,
+    salt: &str,
+) -> bool {
+    let Some((head, rest)) = segments.split_first() else {
+        return false;
+    };
+
+    if rest.is_empty() {
+        return apply_to_children(value, head, action, salt);
+    }
+
+    match (value, *head) {
+        (Value::Array(items), "*") => items
+            .iter_mut()
+            .fold(false, |acc, v| apply_at_path(v, rest, action, salt) | acc),
+        (Value::Object(map), "*") => map
+            .values_mut()
+            .fold(false, |acc, v| apply_at_path(v, rest, action, salt) | acc),
+        (Value::Object(map), key) => map
+            .get_mut(key)
+            .is_some_and(|v| apply_at_path(v, rest, action, salt)),
+        _ => false,
+    }
+}
+
+fn apply_to_children(parent: &mut Value, key: &str, action: RedactionAction, salt: &str) -> bool {
+    match parent {
+        Value::Object(map) if key == "*" => {
+            if action == RedactionAction::Drop {
+                let had_keys = !map.is_empty();
+                map.clear();
+                return had_keys;
+            }
+            map.values_mut()
+                .fold(false, |acc, v| transform(v, action, salt) | acc)
+        }
+        Value::Object(map) => {
+            if action == RedactionAction::Drop {
+                return map.remove(key).is_some();
+            }
+            map.get_mut(key).is_some_and(|v| transform(v, action, salt))
+        }
+        Value::Array(items) if key == "*" => {
+            if action == RedactionAction::Drop {
+                let had_items = !items.is_empty();
+                items.clear();
+                return had_items;
+            }
+            items
+                .iter_mut()
+                .fold(false, |acc, v| transform(v, action, salt) | acc)
+        }
+        _ => false,
+    }
+}
+
+fn transform(value: &mut Value, action: RedactionAction, salt: &str) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Array(items) => items
+            .iter_mut()
+            .fold(false, |acc, v| transform(v, action, salt) | acc),
+        Value::Object(map) => map
+            .values_mut()
+            .fold(false, |acc, v| transform(v, action, salt) | acc),
+        _ => {
+            *value = match action {
+                RedactionAction::Mask => Value::String(mask(value)),
+                RedactionAction::Hash => Value::String(hash(value, salt)),
+                RedactionAction::Drop => Value::Null,
+            };
+            true
+        }
+    }
+}
+
+fn mask(value: &Value) -> String {
+    let Some(s) = value.as_str() else {
+        return MASK.to_string();
+    };
+
+    if let Some((local, domain)) = s.split_once('@') {
+        let first: String = local.chars().take(1).collect();
+        return format!("{first}{MASK}@{domain}");
+    }
+
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() < 8 {
+        return MASK.to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{MASK}{tail}")
+}
+
+fn hash(value: &Value, salt: &str) -> String {
+    let raw = match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(raw.as_bytes());
+    format!("sha256:{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(path: &str, action: RedactionAction) -> RedactionRule {
+        RedactionRule {
+            path: path.to_string(),
+            action,
+            roles: vec![CallerRole::Standard],
+        }
+    }
+
+    #[test]
+    fn mask_keeps_email_domain_and_phone_tail() {
+        let mut profile = json!({
+            "whoIAm": { "email": "asha@example.com", "phone": "+919876543210", "pin": "1234" }
+        });
+        let rules = [
+            rule("whoIAm.email", RedactionAction::Mask),
+            rule("whoIAm.phone", RedactionAction::Mask),
+            rule("whoIAm.pin", RedactionAction::Mask),
+        ];
+        assert!(redact(&mut profile, &rules, CallerRole::Standard, ""));
+        assert_eq!(profile["whoIAm"]["email"], "a****@example.com");
+        assert_eq!(profile["whoIAm"]["phone"], "****3210");
+        assert_eq!(profile["whoIAm"]["pin"], "****");
+    }
+
+    #[test]
+    fn hash_is_salted_and_stable() {
+        let rules = [rule("id", RedactionAction::Hash)];
+        let hashed = |salt: &str| {
+            let mut v = json!({ "id": "12345" });
+            redact(&mut v, &rules, CallerRole::Standard, salt);
+            v["id"].as_str().unwrap().to_string()
+        };
+        assert!(hashed("a").starts_with("sha256:"));
+        assert_eq!(hashed("a"), hashed("a"));
+        assert_ne!(hashed("a"), hashed("b"));
+    }
+
+    #[test]
+    fn drop_removes_keys_and_wildcards_reach_every_item() {
+        let mut profile = json!({
+            "whoIAm": { "gender": "F", "name": "Asha" },
+            "contacts": [{ "phone": "9876543210" }, { "phone": "9123456780" }]
+        });
+        let rules = [
+            rule("whoIAm.gender", RedactionAction::Drop),
+            rule("contacts.*.phone", RedactionAction::Drop),
+        ];
+        assert!(redact(&mut profile, &rules, CallerRole::Standard, ""));
+        assert_eq!(
+            profile,
+            json!({ "whoIAm": { "name": "Asha" }, "contacts": [{}, {}] })
+        );
+    }
+
+    #[test]
+    fn rules_only_apply_to_their_roles() {
+        let mut profile = json!({ "whoIAm": { "email": "asha@example.com" } });
+        let rules = [rule("whoIAm.email", RedactionAction::Drop)];
+        assert!(!redact(&mut profile, &rules, CallerRole::Privileged, ""));
+        assert_eq!(profile["whoIAm"]["email"], "asha@example.com");
+    }
+
+    #[test]
+    fn missing_paths_and_nulls_are_untouched() {
+        let mut profile = json!({ "whoIAm": { "email": null } });
+        let rules = [
+            rule("whoIAm.email", RedactionAction::Mask),
+            rule("location.address", RedactionAction::Drop),
+        ];
+        assert!(!redact(&mut profile, &rules, CallerRole::Standard, ""));
+        assert_eq!(profile, json!({ "whoIAm": { "email": null } }));
+    }
+
+    #[test]
+    fn view_uses_the_policy_for_its_role() {
+        let policy = RedactionConfig::default();
+        let mut profile = json!({ "location": { "address": "12 MG Road", "city": "Pune" } });
+
+        let mut privileged = profile.clone();
+        RedactionView::new(&policy, CallerRole::Privileged).apply(&mut privileged);
+        assert_eq!(privileged["location"]["address"], "12 MG Road");
+
+        RedactionView::new(&policy, CallerRole::Standard).apply(&mut profile);
+        assert_eq!(profile, json!({ "location": { "city": "Pune" } }));
+    }
+}
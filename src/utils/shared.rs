@@ -1,10 +1,13 @@
 use crate::config::AppConfig;
 use crate::utils::http_client::post_json;
+use crate::utils::metrics::record_provider_db;
 use anyhow::Result;
 use serde_json::Value;
 use std::sync::Arc;
-use tracing::info;
+use std::time::Instant;
+use tracing::{info, instrument};
 
+#[instrument(name = "callback", skip_all, fields(action = %format!("on_{action}")))]
 pub async fn send_to_bpp_caller(
     action: &str,
     payload: Value,
@@ -15,7 +18,7 @@ pub async fn send_to_bpp_caller(
         .and_then(|ctx| ctx.get("transaction_id"))
         .and_then(|v| v.as_str())
         .unwrap_or("unknown_txn");
-    let full_action = format!("on_{}", action);
+    let full_action = format!("on_{action}");
 
     info!(
         target: "bpp",
@@ -30,6 +33,7 @@ pub async fn send_to_bpp_caller(
     post_json(&full_url, payload).await
 }
 
+#[instrument(name = "provider_db", skip(payload, config))]
 pub async fn call_provider_db(path: &str, payload: Value, config: &AppConfig) -> Result<Value> {
     let db_url = &config.provider_db.db_uri;
     let full_url = format!(
@@ -37,5 +41,9 @@ pub async fn call_provider_db(path: &str, payload: Value, config: &AppConfig) ->
         db_url.trim_end_matches('/'),
         path.trim_start_matches('/')
     );
-    post_json(&full_url, payload).await
+
+    let started = Instant::now();
+    let result = post_json(&full_url, payload).await;
+    record_provider_db(path, result.is_ok(), started.elapsed());
+    result
 }
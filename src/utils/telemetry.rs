@@ -0,0 +1,145 @@
+use crate::config::{OtlpProtocol, TelemetryConfig, TraceExporter};
+use chrono::{DateTime, Utc};
+use opentelemetry::trace::Status;
+use opentelemetry::{global, Context};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider, SpanData, SpanExporter};
+use opentelemetry_sdk::Resource;
+use reqwest::header::HeaderMap;
+use serde_json::{json, Map, Value};
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Builds the tracer provider for the configured exporter, or `None` when
+/// spans are not exported.
+pub fn tracer_provider(config: &TelemetryConfig) -> anyhow::Result<Option<SdkTracerProvider>> {
+    let builder = SdkTracerProvider::builder()
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio,
+        ))));
+
+    let builder = match config.exporter {
+        TraceExporter::None => return Ok(None),
+        TraceExporter::Otlp => {
+            let exporter = match config.otlp_protocol {
+                OtlpProtocol::Http => {
+                    let mut exporter = opentelemetry_otlp::SpanExporter::builder().with_http();
+                    if let Some(endpoint) = &config.otlp_endpoint {
+                        exporter = exporter.with_endpoint(endpoint);
+                    }
+                    exporter.build()?
+                }
+                OtlpProtocol::Grpc => {
+                    let mut exporter = opentelemetry_otlp::SpanExporter::builder().with_tonic();
+                    if let Some(endpoint) = &config.otlp_endpoint {
+                        exporter = exporter.with_endpoint(endpoint);
+                    }
+                    exporter.build()?
+                }
+            };
+            builder.with_batch_exporter(exporter)
+        }
+        TraceExporter::Stdout => {
+            builder.with_simple_exporter(opentelemetry_stdout::SpanExporter::default())
+        }
+        TraceExporter::File => {
+            builder.with_batch_exporter(FileSpanExporter::open(&config.file_path)?)
+        }
+    };
+
+    Ok(Some(builder.build()))
+}
+
+/// Uses W3C `traceparent`/`tracestate` for propagation across services.
+pub fn install_propagator() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+}
+
+/// Adds the current span's trace context to outbound request headers.
+pub fn inject_trace_context(headers: &mut HeaderMap) {
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers))
+    });
+}
+
+/// Reads the caller's trace context from inbound request headers.
+pub fn extract_trace_context(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Writes finished spans as JSON lines, for running without a collector.
+#[derive(Debug)]
+struct FileSpanExporter {
+    file: Mutex<BufWriter<File>>,
+}
+
+impl FileSpanExporter {
+    fn open(path: &str) -> std::io::Result<Self> {
+        if let Some(dir) = Path::new(path).parent() {
+            create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    fn span_json(span: &SpanData) -> Value {
+        let attributes: Map<String, Value> = span
+            .attributes
+            .iter()
+            .map(|kv| (kv.key.to_string(), json!(kv.value.to_string())))
+            .collect();
+        let status = match &span.status {
+            Status::Unset => json!("unset"),
+            Status::Ok => json!("ok"),
+            Status::Error { description } => json!({ "error": description }),
+        };
+        let duration_ms = span
+            .end_time
+            .duration_since(span.start_time)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0;
+
+        json!({
+            "trace_id": span.span_context.trace_id().to_string(),
+            "span_id": span.span_context.span_id().to_string(),
+            "parent_span_id": span.parent_span_id.to_string(),
+            "name": span.name,
+            "kind": format!("{:?}", span.span_kind),
+            "start": DateTime::<Utc>::from(span.start_time).to_rfc3339(),
+            "duration_ms": duration_ms,
+            "attributes": attributes,
+            "status": status,
+        })
+    }
+}
+
+impl SpanExporter for FileSpanExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+        for span in &batch {
+            writeln!(file, "{}", Self::span_json(span))
+                .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+        }
+        file.flush()
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::config::{AppConfig, CliArgs};
+use crate::cron::CronJobs;
+use crate::state::SharedConfig;
+
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::Mutex;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+/// Settings only read at startup. Changes to them are still logged on
+/// reload but take effect after a restart.
+const RESTART_REQUIRED: &[&str] = &[
+    "http.",
+    "db.",
+    "redis.",
+    "auth.jwt",
+    "encryption",
+    "cors.",
+    "reload.",
+    "metrics.",
+    "telemetry.",
+    "logging.level",
+    "logging.targets",
+    "logging.stdout",
+    "logging.format",
+    "logging.file.",
+    "logging.redaction.",
+];
+
+/// Extensions the config loader tries when the path has none.
+const CONFIG_EXTENSIONS: &[&str] = &["", ".yaml", ".yml", ".json", ".toml"];
+
+fn watched_file(cli: &CliArgs) -> Option<PathBuf> {
+    let (path, _) = cli.config_file()?;
+    CONFIG_EXTENSIONS
+        .iter()
+        .map(|ext| PathBuf::from(format!("{path}{ext}")))
+        .find(|candidate| candidate.is_file())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, value, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.clone());
+        }
+    }
+}
+
+fn flattened(value: &Value) -> BTreeMap<String, Value> {
+    let mut out = BTreeMap::new();
+    flatten("", value, &mut out);
+    out
+}
+
+/// Keys whose values differ between `old` and `new`. Changes are detected
+/// on the real values but reported with secrets masked.
+pub fn config_diff(old: &AppConfig, new: &AppConfig) -> Vec<(String, Value, Value)> {
+    let raw_old = flattened(&serde_json::to_value(old).unwrap_or_default());
+    let raw_new = flattened(&serde_json::to_value(new).unwrap_or_default());
+    let shown_old = flattened(&old.masked());
+    let shown_new = flattened(&new.masked());
+
+    let keys: BTreeSet<&String> = raw_old.keys().chain(raw_new.keys()).collect();
+    keys.into_iter()
+        .filter(|key| raw_old.get(*key) != raw_new.get(*key))
+        .map(|key| {
+            let shown =
+                |values: &BTreeMap<String, Value>| values.get(key).cloned().unwrap_or(Value::Null);
+            (key.clone(), shown(&shown_old), shown(&shown_new))
+        })
+        .collect()
+}
+
+async fn reload(cli: &CliArgs, config: &SharedConfig, cron_jobs: &Mutex<CronJobs>, trigger: &str) {
+    info!("🔁 Reloading config ({})", trigger);
+
+    let new_config = match AppConfig::load(cli) {
+        Ok(new_config) => new_config,
+        Err(e) => {
+            error!(
+                "Config reload rejected, keeping the running config: {}",
+                e.problems.join("; ")
+            );
+            return;
+        }
+    };
+
+    let changes = config_diff(&config.current(), &new_config);
+    if changes.is_empty() {
+        info!("Config reloaded with no changes");
+        return;
+    }
+
+    for (key, old, new) in &changes {
+        if RESTART_REQUIRED
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+        {
+            warn!("⚠️ {}: {} → {} (takes effect after restart)", key, old, new);
+        } else {
+            info!("🔁 {}: {} → {}", key, old, new);
+        }
+    }
+
+    config.replace(new_config);
+
+    if let Err(e) = cron_jobs.lock().await.reschedule().await {
+        error!("Failed to reschedule cron jobs after reload: {:?}", e);
+    }
+
+    info!("✅ Config reloaded ({} changes)", changes.len());
+}
+
+/// Reloads the config on SIGHUP and whenever the config file's mtime
+/// changes. A config that fails to load or validate is logged and ignored.
+pub fn spawn_config_reloader(cli: CliArgs, config: SharedConfig, cron_jobs: Arc<Mutex<CronJobs>>) {
+    let watch_interval = config.current().reload.watch_interval_seconds;
+
+    tokio::spawn(async move {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                error!("Cannot listen for SIGHUP, config reload disabled: {:?}", e);
+                return;
+            }
+        };
+
+        let file = watched_file(&cli).filter(|_| watch_interval > 0);
+        if let Some(file) = &file {
+            info!(
+                "👀 Watching {} for config changes every {}s",
+                file.display(),
+                watch_interval
+            );
+        }
+
+        let mut last_modified = file.as_deref().and_then(modified);
+        let mut ticker = tokio::time::interval(Duration::from_secs(watch_interval.max(1)));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = hangup.recv() => reload(&cli, &config, &cron_jobs, "SIGHUP").await,
+                _ = ticker.tick(), if file.is_some() => {
+                    let current = file.as_deref().and_then(modified);
+                    if current != last_modified {
+                        last_modified = current;
+                        reload(&cli, &config, &cron_jobs, "config file changed").await;
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,103 @@
+use crate::config::AppConfig;
+use crate::db::profile::{fetch_profile_batch, update_beckn_structure};
+use crate::utils::encryption::Keyring;
+
+use anyhow::Context;
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+
+#[derive(Debug, Default)]
+pub struct RotationSummary {
+    pub scanned: u64,
+    pub updated: u64,
+    pub fields_rewrapped: u64,
+    pub fields_encrypted: u64,
+    /// Rows a sync rewrote while they were being re-encrypted. They are
+    /// picked up again by the next run.
+    pub skipped: u64,
+    pub failed: u64,
+}
+
+/// Walks every stored profile in batches, re-wrapping data keys that are
+/// not under the active key and encrypting configured paths still held in
+/// plaintext (e.g. rows stored before encryption was enabled).
+pub async fn run(config: &AppConfig) -> anyhow::Result<RotationSummary> {
+    let encryption = config
+        .encryption
+        .as_ref()
+        .context("encryption is not configured")?;
+    let keyring = Keyring::load(&encryption.keyring_path)?;
+    let db_pool = PgPool::connect(&config.db.url).await?;
+
+    info!(
+        "🔑 Re-encrypting profiles under key {} (batch size {})",
+        keyring.active_kid(),
+        encryption.rotation_batch_size
+    );
+
+    let mut summary = RotationSummary::default();
+    let mut after = None;
+
+    loop {
+        let batch =
+            fetch_profile_batch(&db_pool, after, encryption.rotation_batch_size.max(1)).await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after = Some(last.id);
+
+        for mut row in batch {
+            summary.scanned += 1;
+
+            let changed = keyring
+                .rewrap_all(&mut row.beckn_structure)
+                .and_then(|rewrapped| {
+                    let encrypted = keyring.encrypt_paths(
+                        &mut row.beckn_structure,
+                        &row.profile_id,
+                        &encryption.paths,
+                    )?;
+                    Ok((rewrapped, encrypted))
+                });
+
+            match changed {
+                Ok((0, 0)) => {}
+                Ok((rewrapped, encrypted)) => {
+                    match update_beckn_structure(&db_pool, row.id, &row.hash, &row.beckn_structure)
+                        .await
+                    {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            warn!(
+                                "Profile {} changed during re-encryption, skipping it until the next run",
+                                row.profile_id
+                            );
+                            summary.skipped += 1;
+                            continue;
+                        }
+                        Err(e) => {
+                            error!("Failed to update profile {}: {:?}", row.profile_id, e);
+                            summary.failed += 1;
+                            continue;
+                        }
+                    }
+                    summary.updated += 1;
+                    summary.fields_rewrapped += rewrapped as u64;
+                    summary.fields_encrypted += encrypted as u64;
+                }
+                Err(e) => {
+                    error!("Failed to re-encrypt profile {}: {:?}", row.profile_id, e);
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        info!(
+            "Key rotation progress: scanned={}, updated={}, skipped={}, failed={}",
+            summary.scanned, summary.updated, summary.skipped, summary.failed
+        );
+    }
+
+    info!("✅ Key rotation finished: {:?}", summary);
+    Ok(summary)
+}
@@ -1 +1,3 @@
+pub mod config_reload;
+pub mod key_rotation;
 pub mod processor;
@@ -1,31 +1,45 @@
 use crate::config::AppConfig;
 use crate::models::webhook::Context;
 use crate::services::webhook::generate_response;
+use crate::utils::metrics::record_callback;
 use crate::utils::shared::send_to_bpp_caller;
 use serde_json::Value;
 use std::sync::Arc;
-use tokio::task;
-use tracing::error;
+use tokio_util::task::TaskTracker;
+use tracing::{error, info_span, Instrument};
 
 pub fn spawn_processing_task(
     context: Context,
     message: Value,
     action: String,
     config: Arc<AppConfig>,
+    tasks: &TaskTracker,
 ) {
-    task::spawn({
+    let span = info_span!(
+        "process",
+        transaction_id = %context.transaction_id,
+        message_id = %context.message_id,
+        action = %action,
+    );
+
+    tasks.spawn({
         let config = config.clone();
         async move {
-            match generate_response(&action, context, message, &config).await {
-                Ok(response) => {
-                    if let Err(e) = send_to_bpp_caller(&action, response, config).await {
+            let delivered = match generate_response(&action, context, message, &config).await {
+                Ok(response) => match send_to_bpp_caller(&action, response, config).await {
+                    Ok(_) => true,
+                    Err(e) => {
                         error!("Error sending to BPP client: {:?}", e);
+                        false
                     }
-                }
+                },
                 Err(e) => {
                     error!("Error generating response: {:?}", e);
+                    false
                 }
-            }
+            };
+            record_callback(&format!("on_{action}"), delivered);
         }
+        .instrument(span)
     });
 }